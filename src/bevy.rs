@@ -0,0 +1,208 @@
+//! Integration with the [Bevy](https://bevyengine.org) game engine.
+//!
+//! [`CameraRig`] is a component. Add [`DollyPlugin`] to update every entity with
+//! a `CameraRig` and a Bevy `Transform` from the app's `Time`, and copy the final transform
+//! of the rig to the entity. Driver parameters can be changed in `Update`;
+//! the rigs are updated in `PostUpdate`, before transform propagation.
+//! Only `Time` is required, so this also works headless, e.g. with `MinimalPlugins`.
+//!
+//! Bevy uses a right-handed coordinate system. Transforms of [`LeftHanded`] rigs
+//! are mirrored along the Z axis on conversion, so that the camera still looks
+//! along its forward axis.
+//!
+//! [`CameraRig`]: ../rig/struct.CameraRig.html
+//! [`DollyPlugin`]: struct.DollyPlugin.html
+//! [`LeftHanded`]: ../handedness/struct.LeftHanded.html
+
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    component::{Component, Mutable, StorageType},
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::{Query, Res},
+};
+use bevy_math::{Quat as BevyQuat, Vec3 as BevyVec3};
+use bevy_time::Time;
+use bevy_transform::{components::Transform as BevyTransform, TransformSystems};
+
+use crate::{
+    handedness::{Handedness, RightHanded},
+    rig::CameraRig,
+    transform::Transform,
+};
+
+impl<H: Handedness + Send + Sync> Component for CameraRig<H> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = Mutable;
+}
+
+/// Updates [`CameraRig`]s of handedness `H`, and copies their final transforms
+/// to the Bevy `Transform` of their entities.
+///
+/// [`CameraRig`]: ../rig/struct.CameraRig.html
+pub struct DollyPlugin<H: Handedness = RightHanded> {
+    phantom: PhantomData<fn() -> H>,
+}
+
+impl<H: Handedness> Default for DollyPlugin<H> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<H: Handedness> DollyPlugin<H> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The system set in which [`DollyPlugin`] updates the rigs.
+///
+/// [`DollyPlugin`]: struct.DollyPlugin.html
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DollySystems;
+
+impl<H: Handedness + Send + Sync> Plugin for DollyPlugin<H> {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(PostUpdate, DollySystems.before(TransformSystems::Propagate))
+            .add_systems(PostUpdate, update_camera_rigs::<H>.in_set(DollySystems));
+    }
+}
+
+/// Updates every [`CameraRig`] with the frame time, and copies its final transform
+/// to the Bevy `Transform` of the entity.
+///
+/// [`CameraRig`]: ../rig/struct.CameraRig.html
+pub fn update_camera_rigs<H: Handedness + Send + Sync>(
+    time: Res<Time>,
+    mut rigs: Query<(&mut CameraRig<H>, &mut BevyTransform)>,
+) {
+    for (mut rig, mut transform) in rigs.iter_mut() {
+        let rig_transform = rig.update(time.delta_secs());
+        let rig_transform = BevyTransform::from(rig_transform);
+
+        transform.translation = rig_transform.translation;
+        transform.rotation = rig_transform.rotation;
+    }
+}
+
+// Whether coordinates need to be mirrored between `H` and Bevy's right-handed system.
+fn is_mirrored<H: Handedness>() -> bool {
+    H::FORWARD_Z_SIGN > 0.0
+}
+
+impl<H: Handedness> From<Transform<H>> for BevyTransform {
+    fn from(transform: Transform<H>) -> Self {
+        let mint::Point3 { x, y, z } = transform.position;
+        let mint::Quaternion {
+            v:
+                mint::Vector3 {
+                    x: qx,
+                    y: qy,
+                    z: qz,
+                },
+            s: qw,
+        } = transform.rotation;
+
+        let (translation, rotation) = if is_mirrored::<H>() {
+            (
+                BevyVec3::new(x, y, -z),
+                BevyQuat::from_xyzw(-qx, -qy, qz, qw),
+            )
+        } else {
+            (BevyVec3::new(x, y, z), BevyQuat::from_xyzw(qx, qy, qz, qw))
+        };
+
+        BevyTransform::from_translation(translation).with_rotation(rotation)
+    }
+}
+
+impl<H: Handedness> From<BevyTransform> for Transform<H> {
+    /// Ignores the scale of the Bevy `Transform`.
+    fn from(transform: BevyTransform) -> Self {
+        let BevyVec3 { x, y, z } = transform.translation;
+        let [qx, qy, qz, qw] = transform.rotation.to_array();
+
+        if is_mirrored::<H>() {
+            Transform::from_position_rotation(
+                glam::Vec3::new(x, y, -z),
+                glam::Quat::from_xyzw(-qx, -qy, qz, qw),
+            )
+        } else {
+            Transform::from_position_rotation(
+                glam::Vec3::new(x, y, z),
+                glam::Quat::from_xyzw(qx, qy, qz, qw),
+            )
+        }
+    }
+}
+
+#[test]
+fn bevy_plugin_updates_rigs() {
+    use crate::{
+        drivers::{Position, YawPitch},
+        handedness::LeftHanded,
+    };
+    use bevy_time::{TimePlugin, TimeUpdateStrategy};
+    use core::time::Duration;
+
+    let mut app = App::new();
+    app.add_plugins((
+        TimePlugin,
+        DollyPlugin::<RightHanded>::new(),
+        DollyPlugin::<LeftHanded>::new(),
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+
+    fn rig<H: Handedness>(position: glam::Vec3, yaw_degrees: f32) -> CameraRig<H> {
+        CameraRig::builder()
+            .with(Position::new(position))
+            .with(YawPitch::new().yaw_degrees(yaw_degrees))
+            .build()
+    }
+
+    let right = app
+        .world_mut()
+        .spawn((
+            rig::<RightHanded>(glam::Vec3::new(1.0, 2.0, 3.0), -90.0),
+            BevyTransform::default(),
+        ))
+        .id();
+    let left = app
+        .world_mut()
+        .spawn((
+            rig::<LeftHanded>(glam::Vec3::new(1.0, 2.0, -3.0), 90.0),
+            BevyTransform::default(),
+        ))
+        .id();
+
+    app.update();
+    app.world_mut()
+        .get_mut::<CameraRig<RightHanded>>(right)
+        .unwrap()
+        .driver_mut::<Position>()
+        .translate(glam::Vec3::X);
+    app.update();
+
+    // Turning right takes a negative yaw in right-handed rigs, and a positive one in left-handed.
+    // Both rigs end up looking along +X in Bevy's coordinates.
+    for entity in [right, left] {
+        let transform = app.world().get::<BevyTransform>(entity).unwrap();
+        let expected = if entity == right {
+            BevyVec3::new(2.0, 2.0, 3.0)
+        } else {
+            BevyVec3::new(1.0, 2.0, 3.0)
+        };
+        assert!(transform.translation.distance(expected) < 1e-5);
+        assert!(transform.forward().distance(BevyVec3::X) < 1e-5);
+    }
+
+    let transform = *app.world().get::<BevyTransform>(left).unwrap();
+    let roundtrip = BevyTransform::from(Transform::<LeftHanded>::from(transform));
+    assert!(roundtrip.rotation.angle_between(transform.rotation) < 1e-5);
+}
@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use glam::Vec3;
+
+use crate::math;
+
+/// Scene queries used by [`CollisionArm`] to keep the camera out of geometry.
+///
+/// Implement this on top of the physics engine or spatial structure of your choice.
+///
+/// [`CollisionArm`]: ../drivers/struct.CollisionArm.html
+pub trait CameraCollider: Sync + Send + Debug {
+    /// Sweeps a sphere of the given `radius` from `from` towards `to`.
+    ///
+    /// Returns the distance from `from` at which the sphere first touches geometry,
+    /// or `None` if the path is clear.
+    fn cast(&self, from: mint::Point3<f32>, to: mint::Point3<f32>, radius: f32) -> Option<f32>;
+}
+
+/// A simple [`CameraCollider`] made of planes and spheres.
+///
+/// Useful for tests, prototypes, and simple scenes.
+///
+/// [`CameraCollider`]: trait.CameraCollider.html
+#[derive(Clone, Debug, Default)]
+pub struct PrimitiveCollider {
+    // (normal, offset) pairs; the solid half-space is behind the plane
+    planes: Vec<(Vec3, f32)>,
+    // (center, radius) pairs
+    spheres: Vec<(Vec3, f32)>,
+}
+
+impl PrimitiveCollider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a plane containing all points `p` for which `dot(normal, p) == offset`.
+    ///
+    /// The space behind the plane (opposite to the normal) is treated as solid.
+    pub fn with_plane<V>(mut self, normal: V, offset: f32) -> Self
+    where
+        V: Into<mint::Vector3<f32>>,
+    {
+        let normal: Vec3 = normal.into().into();
+        self.planes.push((normal.normalize(), offset));
+        self
+    }
+
+    /// Add a solid sphere.
+    pub fn with_sphere<P>(mut self, center: P, radius: f32) -> Self
+    where
+        P: Into<mint::Point3<f32>>,
+    {
+        self.spheres.push((center.into().into(), radius));
+        self
+    }
+}
+
+impl CameraCollider for PrimitiveCollider {
+    fn cast(&self, from: mint::Point3<f32>, to: mint::Point3<f32>, radius: f32) -> Option<f32> {
+        let from: Vec3 = from.into();
+        let to: Vec3 = to.into();
+
+        let length = from.distance(to);
+        let dir = (to - from).try_normalize().unwrap_or(Vec3::ZERO);
+
+        let plane_hits = self.planes.iter().filter_map(|&(normal, offset)| {
+            let start_distance = normal.dot(from) - offset;
+            if start_distance < radius {
+                return Some(0.0);
+            }
+
+            let approach = normal.dot(dir);
+            if approach >= 0.0 {
+                return None;
+            }
+
+            Some((radius - start_distance) / approach)
+        });
+
+        let sphere_hits = self.spheres.iter().filter_map(|&(center, sphere_radius)| {
+            let combined_radius = sphere_radius + radius;
+            let m = from - center;
+            let c = m.length_squared() - combined_radius * combined_radius;
+            if c <= 0.0 {
+                return Some(0.0);
+            }
+
+            let b = m.dot(dir);
+            if b > 0.0 {
+                return None;
+            }
+
+            let discriminant = b * b - c;
+            if discriminant < 0.0 {
+                return None;
+            }
+
+            Some(-b - math::sqrt(discriminant))
+        });
+
+        plane_hits
+            .chain(sphere_hits)
+            .filter(|&t| t <= length)
+            .reduce(f32::min)
+    }
+}
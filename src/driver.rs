@@ -1,30 +1,84 @@
+use alloc::boxed::Box;
+use core::any::Any;
+
 use crate::{handedness::Handedness, rig::RigUpdateParams, transform::Transform};
 
+/// Opaque state of a driver, captured by [`RigDriver::snapshot`].
+///
+/// [`RigDriver::snapshot`]: trait.RigDriver.html#method.snapshot
+pub type DriverSnapshot = Box<dyn Any + Send + Sync>;
+
 pub trait RigDriverTraits<H: Handedness>:
-    RigDriver<H> + Sync + Send + std::any::Any + std::fmt::Debug
+    RigDriver<H> + Sync + Send + Any + core::fmt::Debug
 {
     /// Returns `self` as `&dyn Any`
-    fn as_any(&self) -> &dyn std::any::Any;
+    fn as_any(&self) -> &dyn Any;
 
     /// Returns `self` as `&mut dyn Any`
-    fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
-pub trait RigDriver<H: Handedness>: std::any::Any + std::fmt::Debug {
+pub trait RigDriver<H: Handedness>: Any + core::fmt::Debug {
     /// Calculates the transform of this driver component based on the parent
     /// provided in `params`.
     fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H>;
+
+    /// Called when the floating origin of the rig moves; see [`CameraRig::set_origin`].
+    ///
+    /// Drivers which store world-space positions should add `offset` to them,
+    /// so that they stay in place in the world.
+    ///
+    /// [`CameraRig::set_origin`]: ../rig/struct.CameraRig.html#method.set_origin
+    fn rebase(&mut self, _offset: mint::Vector3<f32>) {}
+
+    /// Drops any smoothing state, so that the next update snaps to the parent transform;
+    /// see [`CameraRig::reset_smoothing`].
+    ///
+    /// [`CameraRig::reset_smoothing`]: ../rig/struct.CameraRig.html#method.reset_smoothing
+    fn reset_smoothing(&mut self) {}
+
+    /// Captures the complete state of the driver, including any smoothing,
+    /// for [`CameraRig::snapshot`].
+    ///
+    /// Returns `None` by default, in which case the driver is left untouched when
+    /// restoring snapshots. Drivers which implement `Clone` can use [`snapshot_by_clone`].
+    ///
+    /// [`CameraRig::snapshot`]: ../rig/struct.CameraRig.html#method.snapshot
+    /// [`snapshot_by_clone`]: fn.snapshot_by_clone.html
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        None
+    }
+
+    /// Restores the state previously captured by [`snapshot`](#method.snapshot).
+    fn restore(&mut self, _snapshot: &(dyn Any + Send + Sync)) {}
+}
+
+/// Implements [`RigDriver::snapshot`] for drivers which implement `Clone`.
+///
+/// [`RigDriver::snapshot`]: trait.RigDriver.html#method.snapshot
+pub fn snapshot_by_clone<T: Clone + Send + Sync + 'static>(driver: &T) -> Option<DriverSnapshot> {
+    Some(Box::new(driver.clone()))
+}
+
+/// Implements [`RigDriver::restore`] for drivers which use [`snapshot_by_clone`].
+///
+/// [`RigDriver::restore`]: trait.RigDriver.html#method.restore
+/// [`snapshot_by_clone`]: fn.snapshot_by_clone.html
+pub fn restore_by_clone<T: Clone + 'static>(driver: &mut T, snapshot: &(dyn Any + Send + Sync)) {
+    if let Some(snapshot) = snapshot.downcast_ref::<T>() {
+        *driver = snapshot.clone();
+    }
 }
 
 impl<H: Handedness, T> RigDriverTraits<H> for T
 where
-    T: RigDriver<H> + std::any::Any + Sync + Send + std::fmt::Debug,
+    T: RigDriver<H> + Any + Sync + Send + core::fmt::Debug,
 {
-    fn as_any(&self) -> &dyn std::any::Any {
+    fn as_any(&self) -> &dyn Any {
         self
     }
 
-    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
 }
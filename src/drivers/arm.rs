@@ -1,13 +1,18 @@
-use std::marker::PhantomData;
+use core::any::Any;
+use core::marker::PhantomData;
 
 use glam::{Quat, Vec3};
 
 use crate::{
-    driver::RigDriver, handedness::Handedness, rig::RigUpdateParams, transform::Transform,
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    transform::Transform,
 };
 
 /// Offsets the camera along a vector, in the coordinate space of the parent.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Arm {
     pub offset: mint::Vector3<f32>,
 }
@@ -37,4 +42,12 @@ impl<H: Handedness> RigDriver<H> for Arm {
             phantom: PhantomData,
         }
     }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
 }
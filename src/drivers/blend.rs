@@ -0,0 +1,227 @@
+use alloc::boxed::Box;
+use core::any::Any;
+
+use glam::Vec3;
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    easing::Easing,
+    handedness::Handedness,
+    lens::Lens,
+    rig::{CameraRig, RigSnapshot, RigUpdateParams},
+    transform::Transform,
+};
+
+/// Cross-fades between two nested camera rigs over time.
+///
+/// Both rigs are updated every frame, and their final transforms and lenses are blended
+/// according to the easing curve. The parent transform and lens are ignored, so this is usually
+/// the only driver in a rig, or the first one in the chain.
+#[derive(Debug)]
+pub struct BlendRig<H: Handedness> {
+    /// The rig being transitioned away from
+    pub from: CameraRig<H>,
+
+    /// The rig being transitioned to
+    pub to: CameraRig<H>,
+
+    /// Duration of the transition in seconds
+    pub duration_seconds: f32,
+
+    /// Curve applied to the transition progress
+    pub easing: Easing,
+
+    elapsed_seconds: f32,
+}
+
+struct BlendRigSnapshot<H: Handedness> {
+    from: RigSnapshot<H>,
+    to: RigSnapshot<H>,
+    duration_seconds: f32,
+    easing: Easing,
+    elapsed_seconds: f32,
+}
+
+impl<H: Handedness> BlendRig<H> {
+    pub fn new(from: CameraRig<H>, to: CameraRig<H>, duration_seconds: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration_seconds,
+            easing: Easing::default(),
+            elapsed_seconds: 0.0,
+        }
+    }
+
+    /// Set the curve applied to the transition progress.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Start a new transition from the current target rig to `rig`.
+    ///
+    /// When interrupting a transition which isn't done yet, the new one starts from
+    /// the current blended pose and lens instead, held still, so that the camera doesn't jump.
+    /// The previous target rig is dropped in that case.
+    ///
+    /// The previous source rig is returned, so that it can be reused later.
+    pub fn transition_to(&mut self, rig: CameraRig<H>, duration_seconds: f32) -> CameraRig<H>
+    where
+        H: Send + Sync,
+    {
+        let from = if self.is_done() {
+            core::mem::replace(&mut self.to, rig)
+        } else {
+            let t = self.easing.ease(self.progress());
+            let pose = HeldPose {
+                transform: self.from.final_transform.lerp(&self.to.final_transform, t),
+                lens: self.from.final_lens.lerp(&self.to.final_lens, t),
+            };
+            self.to = rig;
+            CameraRig::builder().with(pose).build()
+        };
+        self.duration_seconds = duration_seconds;
+        self.elapsed_seconds = 0.0;
+        core::mem::replace(&mut self.from, from)
+    }
+
+    /// Linear progress of the transition in the `[0, 1]` range, before easing.
+    pub fn progress(&self) -> f32 {
+        if self.duration_seconds > 0.0 {
+            (self.elapsed_seconds / self.duration_seconds).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns `true` once the `to` rig is fully blended in.
+    pub fn is_done(&self) -> bool {
+        self.progress() >= 1.0
+    }
+}
+
+impl<H: Handedness + Send + Sync> RigDriver<H> for BlendRig<H> {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        self.elapsed_seconds += params.delta_time_seconds;
+
+        let from = self.from.update(params.delta_time_seconds);
+        let to = self.to.update(params.delta_time_seconds);
+        let t = self.easing.ease(self.progress());
+
+        *params.lens = self.from.final_lens.lerp(&self.to.final_lens, t);
+        from.lerp(&to, t)
+    }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        self.from.shift(offset.into());
+        self.to.shift(offset.into());
+    }
+
+    fn reset_smoothing(&mut self) {
+        self.from.reset_smoothing();
+        self.to.reset_smoothing();
+    }
+
+    /// Captures both nested rigs, and the progress of the transition.
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        Some(Box::new(BlendRigSnapshot {
+            from: self.from.snapshot(),
+            to: self.to.snapshot(),
+            duration_seconds: self.duration_seconds,
+            easing: self.easing,
+            elapsed_seconds: self.elapsed_seconds,
+        }))
+    }
+
+    /// Nested rigs are only restored if their drivers still match the snapshot.
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        if let Some(snapshot) = snapshot.downcast_ref::<BlendRigSnapshot<H>>() {
+            let _ = self.from.restore(&snapshot.from);
+            let _ = self.to.restore(&snapshot.to);
+            self.duration_seconds = snapshot.duration_seconds;
+            self.easing = snapshot.easing;
+            self.elapsed_seconds = snapshot.elapsed_seconds;
+        }
+    }
+}
+
+// The pose of an interrupted transition, used as the source of the next one.
+#[derive(Clone, Debug)]
+struct HeldPose<H: Handedness> {
+    transform: Transform<H>,
+    lens: Lens,
+}
+
+impl<H: Handedness + Send + Sync> RigDriver<H> for HeldPose<H> {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        *params.lens = self.lens;
+        self.transform
+    }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        self.transform.position = (Vec3::from(self.transform.position) + Vec3::from(offset)).into();
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn blend_rig_reaches_target() {
+    use crate::drivers::Position;
+
+    let from: CameraRig = CameraRig::builder().with(Position::new(Vec3::X)).build();
+    let to: CameraRig = CameraRig::builder().with(Position::new(Vec3::Y)).build();
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(BlendRig::new(from, to, 1.0).easing(Easing::CubicInOut))
+        .build();
+
+    camera.update(0.5);
+    assert!(!camera.driver::<BlendRig<_>>().is_done());
+
+    let position: Vec3 = camera.update(0.5).position.into();
+    assert!(camera.driver::<BlendRig<_>>().is_done());
+    assert!(position.distance(Vec3::Y) < 1e-5);
+}
+
+#[test]
+fn blend_rig_interrupted_without_jumping() {
+    use crate::drivers::{Fov, Position};
+
+    let rig = |position: Vec3, fov: f32| -> CameraRig {
+        CameraRig::builder()
+            .with(Position::new(position))
+            .with(Fov::new(fov))
+            .build()
+    };
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(BlendRig::new(rig(Vec3::X, 40.0), rig(Vec3::Y, 60.0), 1.0).easing(Easing::Linear))
+        .build();
+    let halfway: Vec3 = camera.update(0.5).position.into();
+    assert!(halfway.distance(Vec3::new(0.5, 0.5, 0.0)) < 1e-5);
+
+    // The new transition starts where the interrupted one was.
+    let previous = camera
+        .driver_mut::<BlendRig<_>>()
+        .transition_to(rig(Vec3::Z, 80.0), 1.0);
+    assert_eq!(Vec3::from(previous.final_transform.position), Vec3::X);
+
+    let position: Vec3 = camera.update(0.0).position.into();
+    assert!(position.distance(halfway) < 1e-5);
+    assert_eq!(camera.final_lens.vertical_fov_degrees, Some(50.0));
+
+    let position: Vec3 = camera.update(0.5).position.into();
+    assert!(position.distance((halfway + Vec3::Z) * 0.5) < 1e-5);
+    assert_eq!(camera.final_lens.vertical_fov_degrees, Some(65.0));
+
+    let position: Vec3 = camera.update(0.5).position.into();
+    assert!(position.distance(Vec3::Z) < 1e-5);
+}
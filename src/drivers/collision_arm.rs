@@ -0,0 +1,188 @@
+use alloc::boxed::Box;
+use core::{any::Any, marker::PhantomData};
+
+use glam::{Quat, Vec3};
+
+use crate::{
+    collision::CameraCollider,
+    driver::{DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    transform::Transform,
+    util::{Smoothed, SmoothingModel, SmoothingParams},
+};
+
+/// Like [`Arm`], but shortens the offset when it would put the camera inside geometry.
+///
+/// The ray from the parent position (the pivot) to the desired camera position is tested
+/// against a user-provided [`CameraCollider`]. The arm pulls in quickly when obstructed,
+/// and eases back out once the obstruction is gone.
+///
+/// [`Arm`]: struct.Arm.html
+/// [`CameraCollider`]: ../collision/trait.CameraCollider.html
+#[derive(Debug)]
+pub struct CollisionArm {
+    /// Offset from the pivot, in the coordinate space of the parent
+    pub offset: mint::Vector3<f32>,
+
+    /// Radius of the sphere swept along the arm
+    pub radius: f32,
+
+    /// Exponential smoothing factor used when the arm gets shorter
+    pub pull_in_smoothness: f32,
+
+    /// Exponential smoothing factor used when the arm gets longer
+    pub ease_out_smoothness: f32,
+
+    /// Scene queries for the arm
+    pub collider: Box<dyn CameraCollider>,
+
+    smoothed_length: Smoothed<f32>,
+}
+
+// Everything but the collider, which is owned by the application.
+#[derive(Clone)]
+struct CollisionArmSnapshot {
+    offset: mint::Vector3<f32>,
+    radius: f32,
+    pull_in_smoothness: f32,
+    ease_out_smoothness: f32,
+    smoothed_length: Smoothed<f32>,
+}
+
+impl CollisionArm {
+    pub fn new<V>(offset: V, collider: impl CameraCollider + 'static) -> Self
+    where
+        V: Into<mint::Vector3<f32>>,
+    {
+        let offset = offset.into();
+
+        Self {
+            offset,
+            radius: 0.2,
+            pull_in_smoothness: 0.0,
+            ease_out_smoothness: 1.0,
+            collider: Box::new(collider),
+            smoothed_length: Default::default(),
+        }
+    }
+
+    /// Set the radius of the sphere swept along the arm.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Set the exponential smoothing factor used when the arm gets shorter.
+    ///
+    /// Zero snaps the camera in front of obstructions immediately, avoiding any clipping.
+    pub fn pull_in_smoothness(mut self, smoothness: f32) -> Self {
+        self.pull_in_smoothness = smoothness;
+        self
+    }
+
+    /// Set the exponential smoothing factor used when the arm gets longer.
+    pub fn ease_out_smoothness(mut self, smoothness: f32) -> Self {
+        self.ease_out_smoothness = smoothness;
+        self
+    }
+
+    /// The current, possibly shortened, length of the arm.
+    pub fn length(&self) -> f32 {
+        self.smoothed_length
+            .get()
+            .unwrap_or_else(|| Vec3::from(self.offset).length())
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for CollisionArm {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        let parent_position: Vec3 = params.parent.position.into();
+        let parent_rotation: Quat = params.parent.rotation.into();
+        let offset = parent_rotation * Vec3::from(self.offset);
+
+        let full_length = offset.length();
+        let desired_position = parent_position + offset;
+
+        let target_length = self
+            .collider
+            .cast(parent_position.into(), desired_position.into(), self.radius)
+            .map_or(full_length, |hit| hit.clamp(0.0, full_length));
+
+        let prev_length = self.smoothed_length.get().unwrap_or(target_length);
+        let smoothness = if target_length < prev_length {
+            self.pull_in_smoothness
+        } else {
+            self.ease_out_smoothness
+        };
+
+        let length = self.smoothed_length.smooth_towards(
+            &target_length,
+            SmoothingParams {
+                smoothness,
+                output_offset_scale: 1.0,
+                delta_time_seconds: params.delta_time_seconds,
+                model: SmoothingModel::Exponential,
+            },
+        );
+
+        let position = parent_position + offset.normalize_or_zero() * length;
+
+        Transform {
+            rotation: params.parent.rotation,
+            position: position.into(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn reset_smoothing(&mut self) {
+        self.smoothed_length.reset();
+    }
+
+    /// Captures everything except for the collider.
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        Some(Box::new(CollisionArmSnapshot {
+            offset: self.offset,
+            radius: self.radius,
+            pull_in_smoothness: self.pull_in_smoothness,
+            ease_out_smoothness: self.ease_out_smoothness,
+            smoothed_length: self.smoothed_length.clone(),
+        }))
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        if let Some(snapshot) = snapshot.downcast_ref::<CollisionArmSnapshot>() {
+            self.offset = snapshot.offset;
+            self.radius = snapshot.radius;
+            self.pull_in_smoothness = snapshot.pull_in_smoothness;
+            self.ease_out_smoothness = snapshot.ease_out_smoothness;
+            self.smoothed_length = snapshot.smoothed_length.clone();
+        }
+    }
+}
+
+#[test]
+fn collision_arm_pulls_in_and_eases_out() {
+    use crate::{collision::PrimitiveCollider, rig::CameraRig};
+
+    // A wall at z = 2, with the solid side towards +Z
+    let wall = PrimitiveCollider::new().with_plane(Vec3::NEG_Z, -2.0);
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(CollisionArm::new(Vec3::Z * 4.0, wall).radius(0.1))
+        .build();
+
+    let position: Vec3 = camera.update(1.0 / 60.0).position.into();
+    assert!(position.distance(Vec3::Z * 1.9) < 1e-4);
+
+    camera.driver_mut::<CollisionArm>().collider = Box::new(PrimitiveCollider::new());
+
+    camera.update(1.0 / 60.0);
+    let length = camera.driver::<CollisionArm>().length();
+    assert!(length > 1.9 && length < 4.0);
+
+    for _ in 0..600 {
+        camera.update(1.0 / 60.0);
+    }
+    assert!((camera.driver::<CollisionArm>().length() - 4.0).abs() < 1e-3);
+}
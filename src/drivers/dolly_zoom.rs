@@ -0,0 +1,103 @@
+use core::any::Any;
+
+use glam::Vec3;
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    math,
+    rig::RigUpdateParams,
+    transform::Transform,
+};
+
+/// Adjusts the field of view to keep a target at a constant size on screen,
+/// while the distance to it changes. Combined with a camera moving towards or away
+/// from the target, this produces the "vertigo" effect.
+///
+/// The position and rotation are passed through; only the lens is changed.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DollyZoom {
+    /// The world-space position to keep framed
+    pub target: mint::Point3<f32>,
+
+    /// The height of the view at the distance of the target, in world units.
+    ///
+    /// If `None`, this is captured on the first update, from the parent field of view
+    /// and the distance to the target at that time.
+    pub frame_height: Option<f32>,
+}
+
+impl DollyZoom {
+    pub fn new<P>(target: P) -> Self
+    where
+        P: Into<mint::Point3<f32>>,
+    {
+        Self {
+            target: target.into(),
+            frame_height: None,
+        }
+    }
+
+    /// Set the height of the view at the distance of the target, in world units.
+    pub fn frame_height(mut self, frame_height: f32) -> Self {
+        self.frame_height = Some(frame_height);
+        self
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for DollyZoom {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        let distance = Vec3::from(params.parent.position).distance(self.target.into());
+
+        if self.frame_height.is_none() {
+            self.frame_height = params
+                .lens
+                .vertical_fov_degrees
+                .map(|fov| 2.0 * distance * math::tan(fov.to_radians() * 0.5));
+        }
+
+        if let Some(frame_height) = self.frame_height {
+            let fov = 2.0 * math::atan2(frame_height * 0.5, distance);
+            params.lens.vertical_fov_degrees = Some(fov.to_degrees());
+        }
+
+        *params.parent
+    }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        self.target = (Vec3::from(self.target) + Vec3::from(offset)).into();
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn dolly_zoom_keeps_frame_height() {
+    use crate::{
+        drivers::{Fov, Position},
+        rig::CameraRig,
+    };
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Position::new(Vec3::new(0.0, 0.0, 10.0)))
+        .with(Fov::new(40.0))
+        .with(DollyZoom::new(Vec3::ZERO))
+        .build();
+
+    assert!((camera.final_lens.vertical_fov_degrees.unwrap() - 40.0).abs() < 1e-4);
+    let frame_height = camera.driver::<DollyZoom>().frame_height.unwrap();
+
+    camera.driver_mut::<Position>().position = Vec3::new(0.0, 0.0, 5.0).into();
+    camera.update(1.0 / 60.0);
+
+    let fov = camera.final_lens.vertical_fov_degrees.unwrap();
+    assert!(fov > 40.0);
+    assert!((2.0 * 5.0 * math::tan(fov.to_radians() * 0.5) - frame_height).abs() < 1e-4);
+}
@@ -0,0 +1,61 @@
+use core::any::Any;
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    transform::Transform,
+};
+
+/// Sets the field of view of the camera, and optionally its clipping planes.
+///
+/// Follow with [`Smooth`] to ease in changes, e.g. for sprint FOV kicks or scope zoom.
+///
+/// [`Smooth`]: struct.Smooth.html
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fov {
+    /// Vertical field of view in degrees
+    pub vertical_fov_degrees: f32,
+
+    /// Distance to the near clipping plane, or `None` to keep the parent's
+    pub near: Option<f32>,
+
+    /// Distance to the far clipping plane, or `None` to keep the parent's
+    pub far: Option<f32>,
+}
+
+impl Fov {
+    pub fn new(vertical_fov_degrees: f32) -> Self {
+        Self {
+            vertical_fov_degrees,
+            near: None,
+            far: None,
+        }
+    }
+
+    /// Also set the distances to the clipping planes.
+    pub fn clip_planes(mut self, near: f32, far: f32) -> Self {
+        self.near = Some(near);
+        self.far = Some(far);
+        self
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for Fov {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        params.lens.vertical_fov_degrees = Some(self.vertical_fov_degrees);
+        params.lens.near = self.near.or(params.lens.near);
+        params.lens.far = self.far.or(params.lens.far);
+
+        *params.parent
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
@@ -0,0 +1,145 @@
+use core::any::Any;
+use core::marker::PhantomData;
+
+use glam::{Quat, Vec3};
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    transform::Transform,
+    util::{exp_smoothing_factor, look_at_with_up, slerp_shortest},
+};
+
+/// Unconstrained rotation for space flight and drone cameras.
+///
+/// Yaw, pitch, and roll increments are applied around the camera's own local axes,
+/// and accumulated in a quaternion, so there is no gimbal lock, and no special orientation.
+/// The angles follow the same conventions as [`YawPitch`], with roll happening around the Z axis.
+///
+/// Roll can optionally be levelled back towards an up vector over time.
+///
+/// [`YawPitch`]: struct.YawPitch.html
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FreeRotation {
+    /// The accumulated rotation
+    pub rotation: mint::Quaternion<f32>,
+
+    /// Exponential smoothing factor for levelling out roll, or `None` to keep any roll
+    pub auto_level_smoothness: Option<f32>,
+
+    /// The up vector to level the roll towards
+    pub world_up: mint::Vector3<f32>,
+}
+
+impl Default for FreeRotation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FreeRotation {
+    /// Creates camera looking forward along Z axis (negative or positive depends on system handedness)
+    pub fn new() -> Self {
+        Self {
+            rotation: Quat::IDENTITY.into(),
+            auto_level_smoothness: None,
+            world_up: Vec3::Y.into(),
+        }
+    }
+
+    /// Set the initial rotation.
+    pub fn rotation_quat<Q>(mut self, rotation: Q) -> Self
+    where
+        Q: Into<mint::Quaternion<f32>>,
+    {
+        self.rotation = rotation.into();
+        self
+    }
+
+    /// Gradually level out roll, with the specified exponential smoothing factor.
+    pub fn auto_level(mut self, smoothness: f32) -> Self {
+        self.auto_level_smoothness = Some(smoothness);
+        self
+    }
+
+    /// Set the up vector to level the roll towards.
+    pub fn world_up<V>(mut self, world_up: V) -> Self
+    where
+        V: Into<mint::Vector3<f32>>,
+    {
+        self.world_up = world_up.into();
+        self
+    }
+
+    /// Additively rotate around the local axes of the camera by the specified angles.
+    pub fn rotate_yaw_pitch_roll(
+        &mut self,
+        yaw_degrees: f32,
+        pitch_degrees: f32,
+        roll_degrees: f32,
+    ) {
+        let rotation: Quat = self.rotation.into();
+        let rotation = rotation
+            * Quat::from_rotation_y(yaw_degrees.to_radians())
+            * Quat::from_rotation_x(pitch_degrees.to_radians())
+            * Quat::from_rotation_z(roll_degrees.to_radians());
+
+        self.rotation = rotation.normalize().into();
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for FreeRotation {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        if let Some(smoothness) = self.auto_level_smoothness {
+            let rotation: Quat = self.rotation.into();
+
+            // Looking straight along the up vector, there is no roll to level out.
+            if let Some(level) = look_at_with_up::<H>(rotation * H::FORWARD, self.world_up.into()) {
+                let t = exp_smoothing_factor(smoothness, params.delta_time_seconds);
+                self.rotation = slerp_shortest(rotation, level, t).into();
+            }
+        }
+
+        Transform {
+            position: params.parent.position,
+            rotation: self.rotation,
+            phantom: PhantomData,
+        }
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn free_rotation_auto_levels_roll() {
+    use crate::rig::CameraRig;
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(FreeRotation::new().auto_level(0.5))
+        .build();
+
+    camera
+        .driver_mut::<FreeRotation>()
+        .rotate_yaw_pitch_roll(30.0, 20.0, 45.0);
+
+    let forward: Vec3 = camera.update(0.0).forward();
+    let up: Vec3 = camera.final_transform.up();
+    assert!(up.dot(Vec3::Y) < 0.9);
+
+    for _ in 0..120 {
+        camera.update(1.0 / 60.0);
+    }
+
+    // Roll is gone, but the camera still faces the same way.
+    let right: Vec3 = camera.final_transform.right();
+    assert!(right.dot(Vec3::Y).abs() < 1e-3);
+    assert!(camera.final_transform.forward::<Vec3>().dot(forward) > 0.9999);
+}
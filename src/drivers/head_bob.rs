@@ -0,0 +1,293 @@
+use core::any::Any;
+use core::f32::consts::{PI, TAU};
+use core::marker::PhantomData;
+
+use glam::{Quat, Vec3};
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    math,
+    rig::RigUpdateParams,
+    transform::Transform,
+    util::exp_smoothing_factor,
+};
+
+/// First-person head bob, strafe sway, and landing dip, on top of the parent transform.
+///
+/// Feed it the movement of the character every frame via [`set_movement`]. The bob advances
+/// with the distance travelled rather than time, so that steps stay in sync with the feet,
+/// and its amplitude grows with speed up to `reference_speed`.
+///
+/// Offsets and rotations are applied in the coordinate space of the parent.
+///
+/// [`set_movement`]: struct.HeadBob.html#method.set_movement
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeadBob {
+    /// Vertical bob at `reference_speed`; the camera dips by this much on every step
+    pub vertical_amplitude: f32,
+
+    /// Side-to-side bob at `reference_speed`
+    pub lateral_amplitude: f32,
+
+    /// Distance travelled per step; a full bob cycle spans two steps
+    pub step_length: f32,
+
+    /// Speed at which the bob and sway reach their full amplitude
+    pub reference_speed: f32,
+
+    /// Roll towards the strafing direction at `reference_speed`, in degrees
+    pub max_roll_degrees: f32,
+
+    /// How far the camera dips when landing
+    pub landing_dip: f32,
+
+    /// Time from landing to the bottom of the dip
+    pub landing_dip_seconds: f32,
+
+    /// Exponential smoothing factor for changes in amplitude and roll
+    pub smoothness: f32,
+
+    /// Horizontal movement speed of the character
+    pub speed: f32,
+
+    /// Speed of the character to the right of the camera; negative when strafing left
+    pub strafe_speed: f32,
+
+    /// Whether the character is on the ground. The bob pauses in the air.
+    pub grounded: bool,
+
+    phase: f32,
+    amplitude: f32,
+    roll_degrees: f32,
+    seconds_since_landing: Option<f32>,
+}
+
+impl Default for HeadBob {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeadBob {
+    pub fn new() -> Self {
+        Self {
+            vertical_amplitude: 0.05,
+            lateral_amplitude: 0.025,
+            step_length: 1.0,
+            reference_speed: 5.0,
+            max_roll_degrees: 2.0,
+            landing_dip: 0.1,
+            landing_dip_seconds: 0.08,
+            smoothness: 0.5,
+            speed: 0.0,
+            strafe_speed: 0.0,
+            grounded: true,
+            phase: 0.0,
+            amplitude: 0.0,
+            roll_degrees: 0.0,
+            seconds_since_landing: None,
+        }
+    }
+
+    /// Set the vertical and lateral bob at `reference_speed`.
+    pub fn amplitude(mut self, vertical: f32, lateral: f32) -> Self {
+        self.vertical_amplitude = vertical;
+        self.lateral_amplitude = lateral;
+        self
+    }
+
+    /// Set the distance travelled per step.
+    pub fn step_length(mut self, step_length: f32) -> Self {
+        self.step_length = step_length;
+        self
+    }
+
+    /// Set the speed at which the bob and sway reach their full amplitude.
+    pub fn reference_speed(mut self, reference_speed: f32) -> Self {
+        self.reference_speed = reference_speed;
+        self
+    }
+
+    /// Set the roll when strafing at `reference_speed`.
+    pub fn max_roll_degrees(mut self, max_roll_degrees: f32) -> Self {
+        self.max_roll_degrees = max_roll_degrees;
+        self
+    }
+
+    /// Set the depth of the landing dip, and the time it takes to reach the bottom.
+    pub fn landing_dip(mut self, depth: f32, seconds: f32) -> Self {
+        self.landing_dip = depth;
+        self.landing_dip_seconds = seconds;
+        self
+    }
+
+    /// Set the exponential smoothing factor for changes in amplitude and roll.
+    pub fn smoothness(mut self, smoothness: f32) -> Self {
+        self.smoothness = smoothness;
+        self
+    }
+
+    /// Update the movement of the character. Landing is detected when `grounded`
+    /// becomes `true` after having been `false`.
+    pub fn set_movement(&mut self, speed: f32, strafe_speed: f32, grounded: bool) {
+        if grounded && !self.grounded {
+            self.seconds_since_landing = Some(0.0);
+        }
+
+        self.speed = speed;
+        self.strafe_speed = strafe_speed;
+        self.grounded = grounded;
+    }
+
+    // Amplitude and roll which the smoothed values approach.
+    fn targets(&self) -> (f32, f32) {
+        let (intensity, strafe) = if self.reference_speed > 0.0 {
+            (
+                self.speed.abs() / self.reference_speed,
+                self.strafe_speed / self.reference_speed,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        if self.grounded {
+            (
+                intensity.min(1.0),
+                -self.max_roll_degrees * strafe.clamp(-1.0, 1.0),
+            )
+        } else {
+            (0.0, 0.0)
+        }
+    }
+
+    // Offset of the landing dip; a critically damped response to the impact,
+    // reaching `-landing_dip` after `landing_dip_seconds`.
+    fn dip(&self) -> f32 {
+        match self.seconds_since_landing {
+            Some(seconds) if self.landing_dip_seconds > 0.0 => {
+                let t = seconds / self.landing_dip_seconds;
+                -self.landing_dip * t * math::exp(1.0 - t)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for HeadBob {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        let dt = params.delta_time_seconds;
+        let (target_amplitude, target_roll) = self.targets();
+
+        let interp_t = exp_smoothing_factor(self.smoothness, dt);
+        self.amplitude += (target_amplitude - self.amplitude) * interp_t;
+        self.roll_degrees += (target_roll - self.roll_degrees) * interp_t;
+
+        if self.grounded && self.step_length > 0.0 {
+            // Two steps per cycle.
+            self.phase += self.speed.abs() * dt / self.step_length * PI;
+            self.phase = math::rem_euclid(self.phase, TAU);
+        }
+
+        if let Some(seconds) = self.seconds_since_landing.as_mut() {
+            *seconds += dt;
+
+            // The dip is practically over after ten times the time to the bottom.
+            if *seconds > self.landing_dip_seconds * 10.0 {
+                self.seconds_since_landing = None;
+            }
+        }
+
+        let vertical =
+            -self.vertical_amplitude * self.amplitude * (1.0 - math::cos(2.0 * self.phase)) * 0.5;
+        let lateral = self.lateral_amplitude * self.amplitude * math::sin(self.phase);
+        let offset = Vec3::new(lateral, vertical + self.dip(), 0.0);
+
+        let parent_position: Vec3 = params.parent.position.into();
+        let parent_rotation: Quat = params.parent.rotation.into();
+
+        Transform {
+            position: (parent_position + parent_rotation * offset).into(),
+            rotation: (parent_rotation * Quat::from_rotation_z(self.roll_degrees.to_radians()))
+                .into(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn reset_smoothing(&mut self) {
+        (self.amplitude, self.roll_degrees) = self.targets();
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn head_bob_follows_distance_and_dips_on_landing() {
+    use crate::rig::CameraRig;
+
+    let dt = 1.0 / 60.0;
+    let rig = || -> CameraRig {
+        CameraRig::builder()
+            .with(HeadBob::new().reference_speed(1.0).smoothness(0.0))
+            .build()
+    };
+
+    // Standing still doesn't bob.
+    let mut camera = rig();
+    assert_eq!(camera.update(dt).position, Vec3::ZERO.into());
+
+    // The bob depends on the distance travelled, not on time.
+    let walk = |speed: f32, frames: usize| {
+        let mut camera = rig();
+        camera
+            .driver_mut::<HeadBob>()
+            .set_movement(speed, 0.0, true);
+        for _ in 0..frames {
+            camera.update(dt);
+        }
+        Vec3::from(camera.final_transform.position)
+    };
+    let slow = walk(1.5, 30);
+    assert!(slow.length() > 0.01);
+    assert!(slow.distance(walk(3.0, 15)) < 1e-5);
+
+    // Strafing right rolls the right side of the camera down.
+    camera.driver_mut::<HeadBob>().set_movement(1.0, 1.0, true);
+    let right: Vec3 = camera.update(dt).right();
+    assert!(right.y < 0.0);
+
+    let mut camera = rig();
+    camera.driver_mut::<HeadBob>().set_movement(0.0, 0.0, false);
+    camera.update(dt);
+    camera.driver_mut::<HeadBob>().set_movement(0.0, 0.0, true);
+
+    let lowest = (0..60)
+        .map(|_| camera.update(dt).position.y)
+        .fold(0.0, f32::min);
+    assert!((lowest + 0.1).abs() < 0.01);
+    assert!(camera.final_transform.position.y.abs() < 1e-3);
+
+    // Resetting smoothing snaps the roll to the strafing target.
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(HeadBob::new().reference_speed(1.0).smoothness(1.0))
+        .build();
+    camera.driver_mut::<HeadBob>().set_movement(0.0, 1.0, true);
+    let rotation: Quat = camera.reset_smoothing().rotation.into();
+    let expected = Quat::from_rotation_z(-camera.driver::<HeadBob>().max_roll_degrees.to_radians());
+    assert!(rotation.angle_between(expected) < 1e-5);
+
+    // Without a reference speed, there's no bob or sway.
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(HeadBob::new().reference_speed(0.0))
+        .build();
+    camera.driver_mut::<HeadBob>().set_movement(1.0, 0.0, true);
+    let rotation: Quat = camera.update(dt).rotation.into();
+    assert_eq!(rotation, Quat::IDENTITY);
+}
@@ -0,0 +1,279 @@
+use alloc::vec::Vec;
+use core::any::Any;
+use core::marker::PhantomData;
+
+use glam::Vec3;
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    easing::Easing,
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    track::PlaybackClock,
+    transform::Transform,
+    util::slerp_shortest,
+};
+
+/// A timestamped camera pose in a [`Keyframes`] animation.
+///
+/// [`Keyframes`]: struct.Keyframes.html
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe {
+    /// Seconds since the start of the animation
+    pub time_seconds: f32,
+
+    pub position: mint::Point3<f32>,
+    pub rotation: mint::Quaternion<f32>,
+
+    /// Vertical field of view in degrees. Keys without one leave the lens unchanged,
+    /// unless the other key of the segment has one.
+    pub vertical_fov_degrees: Option<f32>,
+
+    /// Easing of the segment from this key to the next one
+    pub easing: Easing,
+}
+
+impl Keyframe {
+    pub fn new<P, Q>(time_seconds: f32, position: P, rotation: Q) -> Self
+    where
+        P: Into<mint::Point3<f32>>,
+        Q: Into<mint::Quaternion<f32>>,
+    {
+        Self {
+            time_seconds,
+            position: position.into(),
+            rotation: rotation.into(),
+            vertical_fov_degrees: None,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Set the field of view at this key.
+    pub fn fov(mut self, vertical_fov_degrees: f32) -> Self {
+        self.vertical_fov_degrees = Some(vertical_fov_degrees);
+        self
+    }
+
+    /// Set the easing of the segment starting at this key.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Animates the camera through a sequence of [`Keyframe`]s, e.g. for cinematics.
+///
+/// Before the first key, the camera stays at the first key. Without any keys,
+/// the parent transform is passed through.
+///
+/// Keys passed during playback are reported by [`passed_keys`](#method.passed_keys).
+///
+/// Keys with [`Easing::Custom`] can't be serialized.
+///
+/// [`Easing::Custom`]: ../easing/enum.Easing.html#variant.Custom
+/// [`Keyframe`]: struct.Keyframe.html
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframes {
+    /// Time within the animation, and playback controls
+    pub clock: PlaybackClock,
+
+    keys: Vec<Keyframe>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    passed_keys: Vec<usize>,
+}
+
+impl Keyframes {
+    /// Create an animation from keys in any order.
+    pub fn new(keys: impl IntoIterator<Item = Keyframe>) -> Self {
+        let mut res = Self {
+            clock: PlaybackClock::new(),
+            keys: Vec::new(),
+            passed_keys: Vec::new(),
+        };
+        res.set_keys(keys);
+        res
+    }
+
+    /// Set the playback rate; see [`PlaybackClock::speed`].
+    ///
+    /// [`PlaybackClock::speed`]: ../track/struct.PlaybackClock.html#structfield.speed
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.clock.speed = speed;
+        self
+    }
+
+    /// Wrap around when reaching either end of the animation.
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.clock.looping = looping;
+        self
+    }
+
+    /// Replace the keys, keeping the current time.
+    pub fn set_keys(&mut self, keys: impl IntoIterator<Item = Keyframe>) {
+        self.keys = keys.into_iter().collect();
+        self.keys
+            .sort_by(|a, b| a.time_seconds.total_cmp(&b.time_seconds));
+        self.passed_keys.clear();
+        self.seek(self.clock.time_seconds());
+    }
+
+    /// The keys, sorted by time.
+    pub fn keys(&self) -> &[Keyframe] {
+        &self.keys
+    }
+
+    /// The time of the last key.
+    pub fn duration_seconds(&self) -> f32 {
+        self.keys.last().map_or(0.0, |key| key.time_seconds)
+    }
+
+    /// Jump to `time_seconds` within the animation. Keys skipped over are not reported as passed.
+    pub fn seek(&mut self, time_seconds: f32) {
+        self.clock.seek(time_seconds, self.duration_seconds());
+    }
+
+    /// Whether a non-looping animation has been played to the end.
+    pub fn is_finished(&self) -> bool {
+        self.clock.is_finished(self.duration_seconds())
+    }
+
+    /// Indices of the keys passed during the most recent update, in the order they were passed.
+    ///
+    /// When looping, the key at the end of the animation is passed, but not the one at zero.
+    pub fn passed_keys(&self) -> &[usize] {
+        &self.passed_keys
+    }
+}
+
+// Records keys in `(from, to]` when moving forward, and `[to, from)` when moving back.
+fn pass_keys(keys: &[Keyframe], passed_keys: &mut Vec<usize>, from: f32, to: f32) {
+    if to > from {
+        for (index, key) in keys.iter().enumerate() {
+            if from < key.time_seconds && key.time_seconds <= to {
+                passed_keys.push(index);
+            }
+        }
+    } else if to < from {
+        for (index, key) in keys.iter().enumerate().rev() {
+            if to <= key.time_seconds && key.time_seconds < from {
+                passed_keys.push(index);
+            }
+        }
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for Keyframes {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        let duration = self.duration_seconds();
+        self.passed_keys.clear();
+        let (keys, passed_keys) = (&self.keys, &mut self.passed_keys);
+        self.clock
+            .advance(params.delta_time_seconds, duration, |from, to| {
+                pass_keys(keys, passed_keys, from, to)
+            });
+
+        let time_seconds = self.clock.time_seconds();
+        let next = self
+            .keys
+            .partition_point(|key| key.time_seconds <= time_seconds);
+
+        let (a, b, t) = match next {
+            0 => match self.keys.first() {
+                Some(key) => (key, key, 0.0),
+                None => return *params.parent,
+            },
+            next if next == self.keys.len() => {
+                let key = &self.keys[next - 1];
+                (key, key, 0.0)
+            }
+            next => {
+                let a = &self.keys[next - 1];
+                let b = &self.keys[next];
+                let t = (time_seconds - a.time_seconds) / (b.time_seconds - a.time_seconds);
+                (a, b, a.easing.ease(t))
+            }
+        };
+
+        match (a.vertical_fov_degrees, b.vertical_fov_degrees) {
+            (Some(fov_a), Some(fov_b)) => {
+                params.lens.vertical_fov_degrees = Some(fov_a + (fov_b - fov_a) * t);
+            }
+            (None, None) => {}
+            (fov_a, fov_b) => params.lens.vertical_fov_degrees = fov_b.or(fov_a),
+        }
+
+        let position = Vec3::from(a.position).lerp(b.position.into(), t);
+        let rotation = slerp_shortest(a.rotation.into(), b.rotation.into(), t);
+
+        Transform {
+            position: position.into(),
+            rotation: rotation.into(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        for key in self.keys.iter_mut() {
+            key.position = (Vec3::from(key.position) + Vec3::from(offset)).into();
+        }
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn keyframes_ease_and_report_passed_keys() {
+    use crate::rig::CameraRig;
+    use glam::Quat;
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(
+            Keyframes::new([
+                Keyframe::new(2.0, Vec3::X * 2.0, Quat::IDENTITY).fov(30.0),
+                Keyframe::new(0.0, Vec3::ZERO, Quat::IDENTITY)
+                    .fov(60.0)
+                    .easing(Easing::CubicInOut),
+                Keyframe::new(1.0, Vec3::X, Quat::from_rotation_y(1.0)).easing(Easing::Step),
+            ])
+            .looping(true),
+        )
+        .build();
+
+    // Eased halfway through the first segment; the FOV is only keyed at the ends.
+    camera.update(0.25);
+    let position: Vec3 = camera.final_transform.position.into();
+    assert!(position.x < 0.25);
+    assert_eq!(camera.final_lens.vertical_fov_degrees, Some(60.0));
+
+    // The step segment holds the pose of its first key.
+    camera.update(1.25);
+    let position: Vec3 = camera.final_transform.position.into();
+    assert!(position.distance(Vec3::X) < 1e-5);
+    assert_eq!(camera.final_lens.vertical_fov_degrees, Some(30.0));
+
+    assert_eq!(camera.driver::<Keyframes>().passed_keys(), [1]);
+
+    // Wrapping around passes the last key.
+    camera.update(0.75);
+    let keyframes = camera.driver_mut::<Keyframes>();
+    assert!((keyframes.clock.time_seconds() - 0.25).abs() < 1e-5);
+    assert_eq!(keyframes.passed_keys(), [2]);
+
+    keyframes.clock.speed = -1.0;
+    camera.update(0.5);
+    let keyframes = camera.driver::<Keyframes>();
+    assert_eq!(keyframes.passed_keys(), [0]);
+    assert!((keyframes.clock.time_seconds() - 1.75).abs() < 1e-5);
+
+    // Only the keys of the most recent update are kept.
+    camera.update(0.1);
+    assert!(camera.driver::<Keyframes>().passed_keys().is_empty());
+}
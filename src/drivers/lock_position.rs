@@ -1,11 +1,16 @@
-use std::marker::PhantomData;
+use core::any::Any;
+use core::marker::PhantomData;
 
 use crate::{
-    driver::RigDriver, handedness::Handedness, rig::RigUpdateParams, transform::Transform,
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    transform::Transform,
 };
 
 /// Locks/constrains the position of the camera to one or more axes
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LockPosition {
     x: Option<f32>,
     y: Option<f32>,
@@ -64,4 +69,18 @@ impl<H: Handedness> RigDriver<H> for LockPosition {
             phantom: PhantomData,
         }
     }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        self.x = self.x.map(|x| x + offset.x);
+        self.y = self.y.map(|y| y + offset.y);
+        self.z = self.z.map(|z| z + offset.z);
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
 }
@@ -1,30 +1,39 @@
-use std::marker::PhantomData;
+use core::any::Any;
+use core::marker::PhantomData;
 
 use glam::Vec3;
 
 use crate::{
-    driver::RigDriver,
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
     handedness::Handedness,
     rig::RigUpdateParams,
     transform::Transform,
-    util::{look_at, ExpSmoothed, ExpSmoothingParams},
+    util::{look_at, Smoothed, SmoothingModel, SmoothingParams},
 };
 
 /// Rotates the camera to point at a world-space position.
 ///
 /// The target tracking can be additionally smoothed, and made to look ahead of it.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LookAt {
-    /// Exponential smoothing factor
+    /// Smoothing factor for target position tracking; see [`smoothing_model`]
+    ///
+    /// [`smoothing_model`]: #structfield.smoothing_model
     pub smoothness: f32,
 
+    /// How the tracked position approaches the target
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub smoothing_model: SmoothingModel,
+
     /// The world-space position to look at
     pub target: mint::Point3<f32>,
 
     // The scale with which smoothing should be applied to the target position
     output_offset_scale: f32,
 
-    smoothed_target: ExpSmoothed<Vec3>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    smoothed_target: Smoothed<Vec3>,
 }
 
 impl LookAt {
@@ -36,18 +45,25 @@ impl LookAt {
 
         Self {
             smoothness: 0.0,
+            smoothing_model: SmoothingModel::Exponential,
             output_offset_scale: 1.0,
             target,
             smoothed_target: Default::default(),
         }
     }
 
-    /// Set the exponential smoothing factor for target position tracking.
+    /// Set the smoothing factor for target position tracking.
     pub fn tracking_smoothness(mut self, smoothness: f32) -> Self {
         self.smoothness = smoothness;
         self
     }
 
+    /// Set how the tracked position approaches the target.
+    pub fn tracking_smoothing_model(mut self, smoothing_model: SmoothingModel) -> Self {
+        self.smoothing_model = smoothing_model;
+        self
+    }
+
     /// Reverse target position smoothing, causing the camera to look ahead of it.
     /// This can then be chained with [`Smooth`], to create
     /// a camera that smoothly follows an object, but doesn't lag far behind it.
@@ -63,12 +79,13 @@ impl<H: Handedness> RigDriver<H> for LookAt {
     fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
         let other: Vec3 = self.target.into();
 
-        let target = self.smoothed_target.exp_smooth_towards(
+        let target = self.smoothed_target.smooth_towards(
             &other,
-            ExpSmoothingParams {
+            SmoothingParams {
                 smoothness: self.smoothness,
                 output_offset_scale: self.output_offset_scale,
                 delta_time_seconds: params.delta_time_seconds,
+                model: self.smoothing_model,
             },
         );
 
@@ -81,4 +98,22 @@ impl<H: Handedness> RigDriver<H> for LookAt {
             phantom: PhantomData,
         }
     }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        let offset = Vec3::from(offset);
+        self.target = (Vec3::from(self.target) + offset).into();
+        self.smoothed_target.map(|target| target + offset);
+    }
+
+    fn reset_smoothing(&mut self) {
+        self.smoothed_target.reset();
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
 }
@@ -1,11 +1,24 @@
 mod arm;
+mod blend;
+mod collision_arm;
+mod dolly_zoom;
+mod fov;
+mod free_rotation;
+mod head_bob;
+mod keyframes;
 mod lock_position;
 mod look_at;
+mod orbit;
+mod playback;
 mod position;
 mod rotation;
+mod shake;
 mod smooth;
+mod spline_path;
 mod yaw_pitch;
 
 pub use self::{
-    arm::*, lock_position::*, look_at::*, position::*, rotation::*, smooth::*, yaw_pitch::*,
+    arm::*, blend::*, collision_arm::*, dolly_zoom::*, fov::*, free_rotation::*, head_bob::*,
+    keyframes::*, lock_position::*, look_at::*, orbit::*, playback::*, position::*, rotation::*,
+    shake::*, smooth::*, spline_path::*, yaw_pitch::*,
 };
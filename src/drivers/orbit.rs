@@ -0,0 +1,303 @@
+use core::any::Any;
+use core::marker::PhantomData;
+
+use glam::{EulerRot, Quat, Vec3};
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    math,
+    rig::RigUpdateParams,
+    transform::Transform,
+    util::{Smoothed, SmoothingModel, SmoothingParams},
+};
+
+/// How [`Orbit::zoom`] input changes the distance to the pivot.
+///
+/// [`Orbit::zoom`]: struct.Orbit.html#method.zoom
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ZoomMode {
+    /// Zoom input is added to the distance directly.
+    Linear,
+
+    /// Zoom input scales the distance exponentially, so that every step feels
+    /// the same regardless of how close the camera is.
+    Exponential,
+}
+
+/// Orbits the camera around a pivot point, at a distance which can be zoomed.
+///
+/// Combines the functionality of [`YawPitch`] and [`Arm`], with limits on pitch and distance.
+/// The pivot is relative to the parent position, so `Orbit` can follow a [`Position`].
+///
+/// [`YawPitch`]: struct.YawPitch.html
+/// [`Arm`]: struct.Arm.html
+/// [`Position`]: struct.Position.html
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Orbit {
+    /// The point to orbit around, relative to the parent position
+    pub pivot: mint::Point3<f32>,
+
+    /// Yaw angle in degrees; follows the same conventions as [`YawPitch`]
+    ///
+    /// [`YawPitch`]: struct.YawPitch.html
+    pub yaw_degrees: f32,
+
+    /// Pitch angle in degrees; follows the same conventions as [`YawPitch`]
+    ///
+    /// [`YawPitch`]: struct.YawPitch.html
+    pub pitch_degrees: f32,
+
+    /// Lower limit for `pitch_degrees`
+    pub min_pitch_degrees: f32,
+
+    /// Upper limit for `pitch_degrees`
+    pub max_pitch_degrees: f32,
+
+    /// Target distance from the pivot
+    pub distance: f32,
+
+    /// Lower limit for `distance`
+    pub min_distance: f32,
+
+    /// Upper limit for `distance`
+    pub max_distance: f32,
+
+    /// How zoom input affects the distance
+    pub zoom_mode: ZoomMode,
+
+    /// Exponential smoothing factor for changes in distance
+    pub zoom_smoothness: f32,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    smoothed_distance: Smoothed<f32>,
+}
+
+impl Default for Orbit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Orbit {
+    pub fn new() -> Self {
+        Self {
+            pivot: Vec3::ZERO.into(),
+            yaw_degrees: 0.0,
+            pitch_degrees: 0.0,
+            min_pitch_degrees: -89.0,
+            max_pitch_degrees: 89.0,
+            distance: 5.0,
+            min_distance: 0.1,
+            max_distance: f32::MAX,
+            zoom_mode: ZoomMode::Exponential,
+            zoom_smoothness: 0.0,
+            smoothed_distance: Default::default(),
+        }
+    }
+
+    /// Set the point to orbit around, relative to the parent position.
+    pub fn pivot<P>(mut self, pivot: P) -> Self
+    where
+        P: Into<mint::Point3<f32>>,
+    {
+        self.pivot = pivot.into();
+        self
+    }
+
+    /// Set the yaw angle in degrees.
+    pub fn yaw_degrees(mut self, yaw_degrees: f32) -> Self {
+        self.yaw_degrees = yaw_degrees;
+        self
+    }
+
+    /// Set the pitch angle in degrees. Clamped to the pitch limits.
+    pub fn pitch_degrees(mut self, pitch_degrees: f32) -> Self {
+        self.pitch_degrees = math::clamp(
+            pitch_degrees,
+            self.min_pitch_degrees,
+            self.max_pitch_degrees,
+        );
+        self
+    }
+
+    /// Set the limits of the pitch angle in degrees. Clamps the current pitch.
+    pub fn pitch_limits(mut self, min_pitch_degrees: f32, max_pitch_degrees: f32) -> Self {
+        assert!(
+            min_pitch_degrees <= max_pitch_degrees,
+            "the minimum pitch must not exceed the maximum pitch"
+        );
+
+        self.min_pitch_degrees = min_pitch_degrees;
+        self.max_pitch_degrees = max_pitch_degrees;
+        self.pitch_degrees = math::clamp(self.pitch_degrees, min_pitch_degrees, max_pitch_degrees);
+        self
+    }
+
+    /// Set the distance from the pivot. Clamped to the distance limits.
+    pub fn distance(mut self, distance: f32) -> Self {
+        self.distance = math::clamp(distance, self.min_distance, self.max_distance);
+        self
+    }
+
+    /// Set the limits of the distance from the pivot. Clamps the current distance.
+    pub fn distance_limits(mut self, min_distance: f32, max_distance: f32) -> Self {
+        assert!(
+            min_distance <= max_distance,
+            "the minimum distance must not exceed the maximum distance"
+        );
+
+        self.min_distance = min_distance;
+        self.max_distance = max_distance;
+        self.distance = math::clamp(self.distance, min_distance, max_distance);
+        self
+    }
+
+    /// Set how zoom input affects the distance.
+    pub fn zoom_mode(mut self, zoom_mode: ZoomMode) -> Self {
+        self.zoom_mode = zoom_mode;
+        self
+    }
+
+    /// Set the exponential smoothing factor for changes in distance.
+    pub fn zoom_smoothness(mut self, zoom_smoothness: f32) -> Self {
+        self.zoom_smoothness = zoom_smoothness;
+        self
+    }
+
+    /// Additively rotate by the specified angles. Pitch is clamped to the pitch limits.
+    pub fn rotate_yaw_pitch(&mut self, yaw_degrees: f32, pitch_degrees: f32) {
+        self.yaw_degrees = (self.yaw_degrees + yaw_degrees) % 360_f32;
+        self.pitch_degrees = math::clamp(
+            self.pitch_degrees + pitch_degrees,
+            self.min_pitch_degrees,
+            self.max_pitch_degrees,
+        );
+    }
+
+    /// Move towards the pivot for positive `amount`, and away from it for negative.
+    ///
+    /// In [`ZoomMode::Linear`], `amount` is in world units. In [`ZoomMode::Exponential`],
+    /// the distance is multiplied by `exp(-amount)`.
+    ///
+    /// [`ZoomMode::Linear`]: enum.ZoomMode.html#variant.Linear
+    /// [`ZoomMode::Exponential`]: enum.ZoomMode.html#variant.Exponential
+    pub fn zoom(&mut self, amount: f32) {
+        let distance = match self.zoom_mode {
+            ZoomMode::Linear => self.distance - amount,
+            ZoomMode::Exponential => self.distance * math::exp(-amount),
+        };
+        self.distance = math::clamp(distance, self.min_distance, self.max_distance);
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for Orbit {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        let rotation = Quat::from_euler(
+            EulerRot::YXZ,
+            self.yaw_degrees.to_radians(),
+            self.pitch_degrees.to_radians(),
+            0.0,
+        );
+
+        let distance = self.smoothed_distance.smooth_towards(
+            &self.distance,
+            SmoothingParams {
+                smoothness: self.zoom_smoothness,
+                output_offset_scale: 1.0,
+                delta_time_seconds: params.delta_time_seconds,
+                model: SmoothingModel::Exponential,
+            },
+        );
+
+        let pivot = Vec3::from(params.parent.position) + Vec3::from(self.pivot);
+        let position = pivot - rotation * H::FORWARD * distance;
+
+        Transform {
+            position: position.into(),
+            rotation: rotation.into(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn reset_smoothing(&mut self) {
+        self.smoothed_distance.reset();
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn orbit_zoom_respects_limits() {
+    use crate::rig::CameraRig;
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(
+            Orbit::new()
+                .pivot(Vec3::Y)
+                .distance(4.0)
+                .distance_limits(1.0, 8.0),
+        )
+        .build();
+
+    let position: Vec3 = camera.final_transform.position.into();
+    assert!(position.distance(Vec3::new(0.0, 1.0, 4.0)) < 1e-5);
+
+    camera.driver_mut::<Orbit>().zoom(-10.0);
+    assert_eq!(camera.driver::<Orbit>().distance, 8.0);
+
+    camera.driver_mut::<Orbit>().zoom(core::f32::consts::LN_2);
+    let position: Vec3 = camera.update(1.0 / 60.0).position.into();
+    assert!(position.distance(Vec3::new(0.0, 1.0, 4.0)) < 1e-5);
+}
+
+#[test]
+fn orbit_reset_smoothing_snaps_zoom() {
+    use crate::rig::CameraRig;
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Orbit::new().distance(5.0).zoom_smoothness(1.0))
+        .build();
+
+    camera.driver_mut::<Orbit>().distance = 20.0;
+    camera.update(1.0 / 60.0);
+    assert!(Vec3::from(camera.final_transform.position).length() < 19.0);
+
+    camera.reset_smoothing();
+    let position: Vec3 = camera.final_transform.position.into();
+    assert!((position.length() - 20.0).abs() < 1e-4);
+}
+
+#[test]
+fn orbit_tolerates_inverted_limits() {
+    // The limits are public, so they may be inverted or NaN without going through the setters.
+    let mut orbit = Orbit::new();
+    orbit.min_pitch_degrees = 30.0;
+    orbit.max_pitch_degrees = -30.0;
+    orbit.min_distance = f32::NAN;
+    orbit.max_distance = 2.0;
+
+    orbit.rotate_yaw_pitch(0.0, 10.0);
+    assert_eq!(orbit.pitch_degrees, -30.0);
+    orbit.zoom(-10.0);
+    assert_eq!(orbit.distance, 2.0);
+    orbit.zoom(10.0);
+    assert!(orbit.distance > 0.0 && orbit.distance < 1e-3);
+
+    let orbit = orbit.pitch_degrees(45.0).distance(5.0);
+    assert_eq!((orbit.pitch_degrees, orbit.distance), (-30.0, 2.0));
+}
+
+#[test]
+#[should_panic]
+fn orbit_rejects_inverted_distance_limits() {
+    let _ = Orbit::new().distance_limits(8.0, 1.0);
+}
@@ -0,0 +1,117 @@
+use core::any::Any;
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    track::{CameraTrack, PlaybackClock},
+    transform::Transform,
+};
+
+/// Replays a recorded [`CameraTrack`], interpolating between its samples.
+///
+/// Passes the parent transform through if the track is empty.
+///
+/// [`CameraTrack`]: ../track/struct.CameraTrack.html
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct Playback<H: Handedness> {
+    pub track: CameraTrack<H>,
+
+    /// Time within the track, and playback controls
+    pub clock: PlaybackClock,
+}
+
+impl<H: Handedness> Playback<H> {
+    pub fn new(track: CameraTrack<H>) -> Self {
+        Self {
+            track,
+            clock: PlaybackClock::new(),
+        }
+    }
+
+    /// Set the playback rate; see [`PlaybackClock::speed`].
+    ///
+    /// [`PlaybackClock::speed`]: ../track/struct.PlaybackClock.html#structfield.speed
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.clock.speed = speed;
+        self
+    }
+
+    /// Wrap around when reaching either end of the track.
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.clock.looping = looping;
+        self
+    }
+
+    /// Jump to `time_seconds` within the track, e.g. for scrubbing.
+    pub fn seek(&mut self, time_seconds: f32) {
+        self.clock.seek(time_seconds, self.track.duration_seconds());
+    }
+
+    /// Whether a non-looping track has been played to the end.
+    pub fn is_finished(&self) -> bool {
+        self.clock.is_finished(self.track.duration_seconds())
+    }
+}
+
+impl<H: Handedness + Send + Sync> RigDriver<H> for Playback<H> {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        self.clock.advance(
+            params.delta_time_seconds,
+            self.track.duration_seconds(),
+            |_, _| {},
+        );
+
+        self.track
+            .sample(self.clock.time_seconds())
+            .unwrap_or(*params.parent)
+    }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        self.track.translate(offset);
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn playback_loops_and_scrubs() {
+    use crate::{drivers::Position, handedness::RightHanded, rig::CameraRig};
+    use glam::{Quat, Vec3};
+
+    let mut recorder: CameraRig = CameraRig::builder().with(Position::new(Vec3::ZERO)).build();
+
+    let mut track = CameraTrack::<RightHanded>::new();
+    track.record(0.0, recorder.final_transform);
+    for _ in 0..10 {
+        recorder.driver_mut::<Position>().translate(Vec3::X);
+        track.record(0.1, recorder.update(0.1));
+    }
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Playback::new(track).looping(true))
+        .build();
+
+    let position = |camera: &CameraRig| Vec3::from(camera.final_transform.position);
+
+    camera.update(0.25);
+    assert!(position(&camera).distance(Vec3::X * 2.5) < 1e-4);
+
+    // Wraps around past the end.
+    camera.update(1.0);
+    assert!(position(&camera).distance(Vec3::X * 2.5) < 1e-4);
+
+    camera.driver_mut::<Playback<_>>().clock.pause();
+    camera.driver_mut::<Playback<_>>().seek(0.55);
+    camera.update(1.0);
+    assert!(position(&camera).distance(Vec3::X * 5.5) < 1e-4);
+    assert_eq!(camera.final_transform.rotation, Quat::IDENTITY.into());
+}
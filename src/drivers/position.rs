@@ -1,13 +1,18 @@
-use std::marker::PhantomData;
+use core::any::Any;
+use core::marker::PhantomData;
 
 use glam::Vec3;
 
 use crate::{
-    driver::RigDriver, handedness::Handedness, rig::RigUpdateParams, transform::Transform,
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    transform::Transform,
 };
 
 /// Directly sets the position of the camera
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub position: mint::Point3<f32>,
 }
@@ -49,4 +54,16 @@ impl<H: Handedness> RigDriver<H> for Position {
             phantom: PhantomData,
         }
     }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        self.translate(offset);
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
 }
@@ -1,13 +1,18 @@
-use std::marker::PhantomData;
+use core::any::Any;
+use core::marker::PhantomData;
 
 use glam::Quat;
 
 use crate::{
-    driver::RigDriver, handedness::Handedness, rig::RigUpdateParams, transform::Transform,
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    rig::RigUpdateParams,
+    transform::Transform,
 };
 
 /// Directly sets the rotation of the camera
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rotation {
     pub rotation: mint::Quaternion<f32>,
 }
@@ -39,4 +44,12 @@ impl<H: Handedness> RigDriver<H> for Rotation {
             phantom: PhantomData,
         }
     }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
 }
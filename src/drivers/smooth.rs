@@ -1,29 +1,43 @@
-use std::marker::PhantomData;
+use core::any::Any;
+use core::marker::PhantomData;
 
 use glam::{Quat, Vec3};
 
 use crate::{
-    driver::RigDriver,
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
     handedness::Handedness,
     rig::RigUpdateParams,
     transform::Transform,
-    util::{ExpSmoothed, ExpSmoothingParams},
+    util::{Smoothed, SmoothingModel, SmoothingParams},
 };
 
 /// Smooths the parent transformation.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Smooth {
-    /// Exponential smoothing factor for the position
+    /// Smoothing factor for the position
     pub position_smoothness: f32,
 
-    /// Exponential smoothing factor for the rotation
+    /// Smoothing factor for the rotation
     pub rotation_smoothness: f32,
 
+    /// Smoothing factor for the field of view of the lens. Defaults to zero.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fov_smoothness: f32,
+
+    /// How the position and rotation approach the parent transform
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub smoothing_model: SmoothingModel,
+
     // The scale with which smoothing should be applied
     output_offset_scale: f32,
 
-    smoothed_position: ExpSmoothed<Vec3>,
-    smoothed_rotation: ExpSmoothed<Quat>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    smoothed_position: Smoothed<Vec3>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    smoothed_rotation: Smoothed<Quat>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    smoothed_fov: Smoothed<f32>,
 }
 
 impl Default for Smooth {
@@ -31,9 +45,12 @@ impl Default for Smooth {
         Self {
             position_smoothness: 1.0,
             rotation_smoothness: 1.0,
+            fov_smoothness: 0.0,
+            smoothing_model: SmoothingModel::Exponential,
             output_offset_scale: 1.0,
             smoothed_position: Default::default(),
             smoothed_rotation: Default::default(),
+            smoothed_fov: Default::default(),
         }
     }
 }
@@ -77,6 +94,25 @@ impl Smooth {
         self.output_offset_scale = if predictive { -1.0 } else { 1.0 };
         self
     }
+
+    /// Also smooth the field of view of the lens, e.g. to ease in changes made by [`Fov`].
+    ///
+    /// [`Fov`]: struct.Fov.html
+    pub fn fov_smoothness(mut self, fov_smoothness: f32) -> Self {
+        self.fov_smoothness = fov_smoothness;
+        self
+    }
+
+    /// Set how the position and rotation approach the parent transform.
+    ///
+    /// [`SmoothingModel::Spring`] avoids sudden changes in velocity when the parent
+    /// starts or stops moving.
+    ///
+    /// [`SmoothingModel::Spring`]: enum.SmoothingModel.html#variant.Spring
+    pub fn smoothing_model(mut self, smoothing_model: SmoothingModel) -> Self {
+        self.smoothing_model = smoothing_model;
+        self
+    }
 }
 
 impl<H: Handedness> RigDriver<H> for Smooth {
@@ -84,28 +120,156 @@ impl<H: Handedness> RigDriver<H> for Smooth {
         let parent_position = From::from(params.parent.position);
         let parent_rotation = From::from(params.parent.rotation);
 
-        let position = self.smoothed_position.exp_smooth_towards(
+        let position = self.smoothed_position.smooth_towards(
             &parent_position,
-            ExpSmoothingParams {
+            SmoothingParams {
                 smoothness: self.position_smoothness,
                 output_offset_scale: self.output_offset_scale,
                 delta_time_seconds: params.delta_time_seconds,
+                model: self.smoothing_model,
             },
         );
 
-        let rotation = self.smoothed_rotation.exp_smooth_towards(
+        let rotation = self.smoothed_rotation.smooth_towards(
             &parent_rotation,
-            ExpSmoothingParams {
+            SmoothingParams {
                 smoothness: self.rotation_smoothness,
                 output_offset_scale: self.output_offset_scale,
                 delta_time_seconds: params.delta_time_seconds,
+                model: self.smoothing_model,
             },
         );
 
+        if let Some(fov) = params.lens.vertical_fov_degrees {
+            params.lens.vertical_fov_degrees = Some(self.smoothed_fov.smooth_towards(
+                &fov,
+                SmoothingParams {
+                    smoothness: self.fov_smoothness,
+                    output_offset_scale: self.output_offset_scale,
+                    delta_time_seconds: params.delta_time_seconds,
+                    model: self.smoothing_model,
+                },
+            ));
+        }
+
         Transform {
             position: position.into(),
             rotation: rotation.into(),
             phantom: PhantomData,
         }
     }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        self.smoothed_position
+            .map(|position| position + Vec3::from(offset));
+    }
+
+    fn reset_smoothing(&mut self) {
+        self.smoothed_position.reset();
+        self.smoothed_rotation.reset();
+        self.smoothed_fov.reset();
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn spring_smoothing_starts_and_stops_gradually() {
+    use crate::{drivers::Position, rig::CameraRig};
+
+    // Returns the final position, and the largest per-frame change in speed.
+    fn follow(model: SmoothingModel) -> (f32, f32) {
+        let mut camera: CameraRig = CameraRig::builder()
+            .with(Position::new(Vec3::ZERO))
+            .with(Smooth::new_position(1.0).smoothing_model(model))
+            .build();
+
+        camera.driver_mut::<Position>().position = Vec3::X.into();
+
+        let dt = 1.0 / 60.0;
+        let mut x = 0.0;
+        let mut speed = 0.0;
+        let mut max_speed_change: f32 = 0.0;
+        for _ in 0..600 {
+            let next_x = camera.update(dt).position.x;
+            let next_speed = (next_x - x) / dt;
+            max_speed_change = max_speed_change.max((next_speed - speed).abs());
+
+            // A critically damped spring never overshoots a stationary target.
+            assert!(next_x <= 1.0 + 1e-5);
+
+            x = next_x;
+            speed = next_speed;
+        }
+
+        (x, max_speed_change)
+    }
+
+    let (exp_x, exp_speed_change) = follow(SmoothingModel::Exponential);
+    let (spring_x, spring_speed_change) = follow(SmoothingModel::Spring);
+
+    assert!((exp_x - 1.0).abs() < 1e-3);
+    assert!((spring_x - 1.0).abs() < 1e-3);
+    assert!(spring_speed_change < exp_speed_change * 0.5);
+
+    // Both models cover half of the distance in the same time for a given smoothness factor,
+    // which is set separately for every channel.
+    let half_way = |model: SmoothingModel, smoothness: f32| {
+        let mut camera: CameraRig = CameraRig::builder()
+            .with(Position::new(Vec3::ZERO))
+            .with(Smooth::new_position_rotation(smoothness, 0.1).smoothing_model(model))
+            .build();
+        camera.driver_mut::<Position>().position = Vec3::X.into();
+
+        let mut elapsed: f32 = 0.0;
+        while camera.final_transform.position.x < 0.5 {
+            camera.update(1e-3);
+            elapsed += 1e-3;
+        }
+        elapsed
+    };
+    let spring_half_life = half_way(SmoothingModel::Spring, 2.0);
+    assert!((spring_half_life - half_way(SmoothingModel::Exponential, 2.0)).abs() < 2e-3);
+    assert!((spring_half_life - 2.0 * half_way(SmoothingModel::Spring, 1.0)).abs() < 2e-3);
+}
+
+#[test]
+fn rotation_smoothing_takes_shortest_path() {
+    use crate::{drivers::Rotation, rig::CameraRig};
+
+    let start = Quat::from_rotation_y(170_f32.to_radians());
+    let target = Quat::from_rotation_y((-170_f32).to_radians());
+
+    let rig = || -> CameraRig {
+        CameraRig::builder()
+            .with(Rotation::new(start))
+            .with(Smooth::new_rotation(1.0))
+            .build()
+    };
+
+    // -190 degrees is the same orientation as 170, but with the opposite sign.
+    let mut camera = rig();
+    camera.driver_mut::<Rotation>().rotation =
+        Quat::from_rotation_y((-190_f32).to_radians()).into();
+    let rotation: Quat = camera.update(1.0 / 60.0).rotation.into();
+    assert!(rotation.angle_between(start) < 1e-3);
+
+    // Crossing 180 degrees should go the short way, rather than through 0.
+    camera.driver_mut::<Rotation>().rotation = target.into();
+    for _ in 0..30 {
+        let rotation: Quat = camera.update(1.0 / 60.0).rotation.into();
+        assert!((rotation * -Vec3::Z).z > 0.98);
+    }
+
+    // A single long step matches many short ones.
+    let mut other = rig();
+    other.driver_mut::<Rotation>().rotation = target.into();
+    let rotation: Quat = other.update(0.5).rotation.into();
+    assert!(rotation.angle_between(camera.final_transform.rotation.into()) < 1e-3);
 }
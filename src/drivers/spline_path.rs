@@ -0,0 +1,433 @@
+use alloc::vec::Vec;
+use core::any::Any;
+use core::marker::PhantomData;
+
+use glam::Vec3;
+
+use crate::{
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    math,
+    rig::RigUpdateParams,
+    transform::Transform,
+    util::look_at,
+};
+
+// Resolution of the table used to map arc length to the spline parameter.
+const ARC_LENGTH_SAMPLES_PER_SEGMENT: usize = 16;
+
+/// The type of curve passing through the control points of a [`SplinePath`].
+///
+/// [`SplinePath`]: struct.SplinePath.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplineKind {
+    /// Uniform Catmull-Rom spline passing through every control point.
+    CatmullRom,
+
+    /// Centripetal Catmull-Rom spline passing through every control point.
+    /// Avoids cusps and self-intersections when control points are unevenly spaced.
+    Centripetal,
+
+    /// Piecewise cubic Bézier curve. Every segment is defined by an anchor point,
+    /// two handles, and the next anchor point, so that a path has `3 * n + 1` points,
+    /// or `3 * n` points when looping.
+    Bezier,
+}
+
+/// Moves the camera along a spline through world-space control points.
+///
+/// The position along the path is tracked by arc length, so that `speed`
+/// is in world units per second regardless of the spacing of control points.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SerializedSplinePath"))]
+pub struct SplinePath {
+    /// Distance travelled along the path per second; can be negative
+    pub speed: f32,
+
+    /// Whether to rotate the camera to face along the path
+    pub align_rotation: bool,
+
+    kind: SplineKind,
+    looping: bool,
+    points: Vec<Vec3>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    arc_lengths: Vec<f32>,
+    distance: f32,
+}
+
+// The serialized fields of `SplinePath`. The arc length table is derived from the points,
+// so it's rebuilt rather than trusted.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedSplinePath {
+    speed: f32,
+    align_rotation: bool,
+    kind: SplineKind,
+    looping: bool,
+    points: Vec<Vec3>,
+    distance: f32,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedSplinePath> for SplinePath {
+    fn from(path: SerializedSplinePath) -> Self {
+        let mut res = Self::new(path.kind, path.points)
+            .looping(path.looping)
+            .speed(path.speed)
+            .align_rotation(path.align_rotation);
+        res.set_distance(path.distance);
+        res
+    }
+}
+
+impl SplinePath {
+    pub fn new<P>(kind: SplineKind, points: impl IntoIterator<Item = P>) -> Self
+    where
+        P: Into<mint::Point3<f32>>,
+    {
+        let mut res = Self {
+            speed: 0.0,
+            align_rotation: false,
+            kind,
+            looping: false,
+            points: Vec::new(),
+            arc_lengths: Vec::new(),
+            distance: 0.0,
+        };
+        res.set_points(points);
+        res
+    }
+
+    /// Create a uniform Catmull-Rom path.
+    pub fn catmull_rom<P>(points: impl IntoIterator<Item = P>) -> Self
+    where
+        P: Into<mint::Point3<f32>>,
+    {
+        Self::new(SplineKind::CatmullRom, points)
+    }
+
+    /// Create a centripetal Catmull-Rom path.
+    pub fn centripetal<P>(points: impl IntoIterator<Item = P>) -> Self
+    where
+        P: Into<mint::Point3<f32>>,
+    {
+        Self::new(SplineKind::Centripetal, points)
+    }
+
+    /// Create a piecewise cubic Bézier path.
+    pub fn bezier<P>(points: impl IntoIterator<Item = P>) -> Self
+    where
+        P: Into<mint::Point3<f32>>,
+    {
+        Self::new(SplineKind::Bezier, points)
+    }
+
+    /// Connect the last control point back to the first one, and wrap around when reaching the end.
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self.rebuild_arc_lengths();
+        self.set_distance(self.distance);
+        self
+    }
+
+    /// Set the distance travelled along the path per second.
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Rotate the camera to face along the path.
+    pub fn align_rotation(mut self, align_rotation: bool) -> Self {
+        self.align_rotation = align_rotation;
+        self
+    }
+
+    /// Replace the control points, keeping the current distance along the path.
+    pub fn set_points<P>(&mut self, points: impl IntoIterator<Item = P>)
+    where
+        P: Into<mint::Point3<f32>>,
+    {
+        self.points = points.into_iter().map(|p| Vec3::from(p.into())).collect();
+        self.rebuild_arc_lengths();
+        self.set_distance(self.distance);
+    }
+
+    /// Total length of the path.
+    pub fn length(&self) -> f32 {
+        self.arc_lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// Current distance along the path.
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    /// Jump to the specified distance along the path.
+    pub fn set_distance(&mut self, distance: f32) {
+        self.distance = self.wrap_distance(distance);
+    }
+
+    /// Current position along the path, normalized by its length to the `[0, 1]` range.
+    pub fn t(&self) -> f32 {
+        let length = self.length();
+        if length > 0.0 {
+            self.distance / length
+        } else {
+            0.0
+        }
+    }
+
+    /// Jump to a position along the path, normalized by its length to the `[0, 1]` range.
+    pub fn set_t(&mut self, t: f32) {
+        self.set_distance(t * self.length());
+    }
+
+    /// Returns `true` when a non-looping path has reached either of its ends in the direction of travel.
+    pub fn is_finished(&self) -> bool {
+        !self.looping
+            && ((self.speed > 0.0 && self.distance >= self.length())
+                || (self.speed < 0.0 && self.distance <= 0.0))
+    }
+
+    /// Evaluate the world-space position at the specified distance along the path.
+    pub fn position_at_distance<P>(&self, distance: f32) -> P
+    where
+        P: From<mint::Point3<f32>>,
+    {
+        From::from(self.eval_at_distance(self.wrap_distance(distance)).into())
+    }
+
+    fn wrap_distance(&self, distance: f32) -> f32 {
+        let length = self.length();
+        if length <= 0.0 {
+            0.0
+        } else if self.looping {
+            math::rem_euclid(distance, length)
+        } else {
+            distance.clamp(0.0, length)
+        }
+    }
+
+    fn segment_count(&self) -> usize {
+        let n = self.points.len();
+        match self.kind {
+            SplineKind::CatmullRom | SplineKind::Centripetal => {
+                if self.looping && n >= 3 {
+                    n
+                } else {
+                    n.saturating_sub(1)
+                }
+            }
+            SplineKind::Bezier => {
+                if self.looping {
+                    n / 3
+                } else {
+                    n.saturating_sub(1) / 3
+                }
+            }
+        }
+    }
+
+    fn rebuild_arc_lengths(&mut self) {
+        let samples = self.segment_count() * ARC_LENGTH_SAMPLES_PER_SEGMENT;
+
+        self.arc_lengths.clear();
+        if samples == 0 {
+            return;
+        }
+
+        let mut length = 0.0;
+        let mut prev = self.eval(0.0);
+        self.arc_lengths.push(0.0);
+
+        for i in 1..=samples {
+            let p = self.eval(i as f32 / ARC_LENGTH_SAMPLES_PER_SEGMENT as f32);
+            length += p.distance(prev);
+            prev = p;
+            self.arc_lengths.push(length);
+        }
+    }
+
+    fn eval_at_distance(&self, distance: f32) -> Vec3 {
+        if self.arc_lengths.len() < 2 {
+            return self.points.first().copied().unwrap_or_default();
+        }
+
+        // Find the arc length table entry containing `distance`, and interpolate within it.
+        let i = self
+            .arc_lengths
+            .partition_point(|&d| d <= distance)
+            .clamp(1, self.arc_lengths.len() - 1);
+        let (d0, d1) = (self.arc_lengths[i - 1], self.arc_lengths[i]);
+        let frac = if d1 > d0 {
+            (distance - d0) / (d1 - d0)
+        } else {
+            0.0
+        };
+
+        self.eval(((i - 1) as f32 + frac) / ARC_LENGTH_SAMPLES_PER_SEGMENT as f32)
+    }
+
+    // Evaluates the spline at the parameter `t` in the `[0, segment_count]` range.
+    fn eval(&self, t: f32) -> Vec3 {
+        let segment_count = self.segment_count();
+        let segment = (math::floor(t) as usize).min(segment_count - 1);
+        let u = t - segment as f32;
+
+        match self.kind {
+            SplineKind::CatmullRom => self.eval_catmull_rom(segment, u, 0.0),
+            SplineKind::Centripetal => self.eval_catmull_rom(segment, u, 0.5),
+            SplineKind::Bezier => {
+                let n = self.points.len();
+                let i = segment * 3;
+                let p0 = self.points[i];
+                let p1 = self.points[i + 1];
+                let p2 = self.points[i + 2];
+                let p3 = self.points[(i + 3) % n];
+
+                let v = 1.0 - u;
+                p0 * (v * v * v)
+                    + p1 * (3.0 * v * v * u)
+                    + p2 * (3.0 * v * u * u)
+                    + p3 * (u * u * u)
+            }
+        }
+    }
+
+    // Barry-Goldman pyramidal evaluation of a Catmull-Rom segment with knot parameterization `alpha`.
+    fn eval_catmull_rom(&self, segment: usize, u: f32, alpha: f32) -> Vec3 {
+        let n = self.points.len() as isize;
+        let point = |i: isize| -> Vec3 {
+            if self.looping {
+                self.points[i.rem_euclid(n) as usize]
+            } else if i < 0 {
+                // Extrapolate beyond the ends, rather than duplicating the end points,
+                // so that the knot intervals never collapse to zero.
+                self.points[0] * 2.0 - self.points[1]
+            } else if i >= n {
+                self.points[n as usize - 1] * 2.0 - self.points[n as usize - 2]
+            } else {
+                self.points[i as usize]
+            }
+        };
+
+        let i = segment as isize;
+        let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+
+        let knot = |a: Vec3, b: Vec3| math::powf(a.distance(b), alpha).max(1e-4);
+        let t0 = 0.0;
+        let t1 = t0 + knot(p0, p1);
+        let t2 = t1 + knot(p1, p2);
+        let t3 = t2 + knot(p2, p3);
+        let t = t1 + (t2 - t1) * u;
+
+        let a1 = p0 * ((t1 - t) / (t1 - t0)) + p1 * ((t - t0) / (t1 - t0));
+        let a2 = p1 * ((t2 - t) / (t2 - t1)) + p2 * ((t - t1) / (t2 - t1));
+        let a3 = p2 * ((t3 - t) / (t3 - t2)) + p3 * ((t - t2) / (t3 - t2));
+
+        let b1 = a1 * ((t2 - t) / (t2 - t0)) + a2 * ((t - t0) / (t2 - t0));
+        let b2 = a2 * ((t3 - t) / (t3 - t1)) + a3 * ((t - t1) / (t3 - t1));
+
+        b1 * ((t2 - t) / (t2 - t1)) + b2 * ((t - t1) / (t2 - t1))
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for SplinePath {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        self.set_distance(self.distance + self.speed * params.delta_time_seconds);
+
+        if self.arc_lengths.is_empty() {
+            return Transform {
+                position: self
+                    .points
+                    .first()
+                    .map_or(params.parent.position, |&p| p.into()),
+                rotation: params.parent.rotation,
+                phantom: PhantomData,
+            };
+        }
+
+        let position = self.eval_at_distance(self.distance);
+
+        let rotation = if self.align_rotation {
+            // Estimate the tangent with a central difference along the path.
+            const EPSILON: f32 = 1e-2;
+            let ahead = self.eval_at_distance(self.wrap_distance(self.distance + EPSILON));
+            let behind = self.eval_at_distance(self.wrap_distance(self.distance - EPSILON));
+            let tangent = ahead - behind;
+
+            if tangent.length_squared() > 0.0 {
+                look_at::<H, _, _>(tangent)
+            } else {
+                params.parent.rotation
+            }
+        } else {
+            params.parent.rotation
+        };
+
+        Transform {
+            position: position.into(),
+            rotation,
+            phantom: PhantomData,
+        }
+    }
+
+    fn rebase(&mut self, offset: mint::Vector3<f32>) {
+        let offset = Vec3::from(offset);
+        for point in &mut self.points {
+            *point += offset;
+        }
+    }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn spline_path_passes_through_end_points() {
+    use crate::rig::CameraRig;
+
+    let points = [Vec3::ZERO, Vec3::X, Vec3::new(1.0, 0.0, -1.0), Vec3::NEG_Z];
+
+    for path in [
+        SplinePath::catmull_rom(points),
+        SplinePath::centripetal(points),
+        SplinePath::bezier(points),
+    ] {
+        let length = path.length();
+        let mut camera: CameraRig = CameraRig::builder().with(path.speed(1.0)).build();
+
+        let start: Vec3 = camera.final_transform.position.into();
+        assert!(start.distance(Vec3::ZERO) < 1e-5);
+
+        let end: Vec3 = camera.update(length + 1.0).position.into();
+        assert!(end.distance(Vec3::NEG_Z) < 1e-4);
+        assert!(camera.driver::<SplinePath>().is_finished());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn spline_path_rebuilds_arc_lengths_when_deserialized() {
+    let path = SplinePath::catmull_rom([Vec3::ZERO, Vec3::X]);
+    let mut json = serde_json::to_value(&path).unwrap();
+    assert!(json.get("arc_lengths").is_none());
+
+    // Edited points, and a stale table, which must not be trusted.
+    json["points"] = serde_json::json!([[0.0, 0.0, 0.0], [3.0, 0.0, 0.0]]);
+    json["arc_lengths"] = serde_json::json!([]);
+    json["distance"] = serde_json::json!(2.0);
+
+    let path: SplinePath = serde_json::from_value(json).unwrap();
+    assert!((path.length() - 3.0).abs() < 1e-4);
+    assert_eq!(path.distance(), 2.0);
+    let position: Vec3 = path.position_at_distance(2.0);
+    assert!(position.distance(Vec3::X * 2.0) < 1e-4);
+}
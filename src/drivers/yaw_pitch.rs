@@ -1,9 +1,14 @@
-use std::marker::PhantomData;
+use core::any::Any;
+use core::marker::PhantomData;
 
 use glam::{EulerRot, Quat};
 
 use crate::{
-    driver::RigDriver, handedness::Handedness, rig::RigUpdateParams, transform::Transform,
+    driver::{restore_by_clone, snapshot_by_clone, DriverSnapshot, RigDriver},
+    handedness::Handedness,
+    math,
+    rig::RigUpdateParams,
+    transform::Transform,
 };
 
 /// Calculate camera rotation based on yaw and pitch angles.
@@ -14,17 +19,118 @@ use crate::{
 /// a positive value of pitch.
 ///
 /// [`right-hand rule`]: https://en.wikipedia.org/wiki/Right-hand_rule#Curve_orientation_and_normal_vectors
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct YawPitch {
-    /// [0..720)
+    /// (-360..360)
     ///
-    /// Note: Quaternions can encode 720 degrees of rotation, causing a slerp from 350 to 0 degrees
-    /// to happen counter-intuitively in the negative direction; the positive direction would go through 720,
-    /// thus being farther. By encoding rotation here in the 0..720 range, we reduce the risk of this happening.
+    /// Smoothing always takes the shortest path between rotations, so wrapping around
+    /// from 359 to 0 degrees does not cause the camera to spin the other way.
+    ///
+    /// When `yaw_limits` are set, this is instead kept within the limits around their reference heading.
     pub yaw_degrees: f32,
 
-    /// [-90..90]
+    /// Between [`min_pitch_degrees`] and [`max_pitch_degrees`]
+    ///
+    /// [`min_pitch_degrees`]: #structfield.min_pitch_degrees
+    /// [`max_pitch_degrees`]: #structfield.max_pitch_degrees
     pub pitch_degrees: f32,
+
+    /// Lower limit of the pitch angle applied by [`rotate_yaw_pitch`]. Defaults to -90.
+    ///
+    /// [`rotate_yaw_pitch`]: #method.rotate_yaw_pitch
+    pub min_pitch_degrees: f32,
+
+    /// Upper limit of the pitch angle applied by [`rotate_yaw_pitch`]. Defaults to 90.
+    ///
+    /// [`rotate_yaw_pitch`]: #method.rotate_yaw_pitch
+    pub max_pitch_degrees: f32,
+
+    /// Keeps the pitch this many degrees away from straight up and down.
+    ///
+    /// Near the poles, yaw and roll become ambiguous, and small changes in input
+    /// can flip the camera around. Defaults to 0.
+    pub pole_margin_degrees: f32,
+
+    /// Optional limits of the yaw angle, e.g. for turrets
+    pub yaw_limits: Option<YawLimits>,
+}
+
+/// Limits of the yaw angle of [`YawPitch`], relative to a reference heading.
+///
+/// [`YawPitch`]: struct.YawPitch.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YawLimits {
+    /// The heading the limits are relative to, in degrees. Update it when the base turns,
+    /// e.g. when the turret is mounted on a vehicle.
+    pub reference_yaw_degrees: f32,
+
+    /// Lower limit relative to the reference heading
+    pub min_degrees: f32,
+
+    /// Upper limit relative to the reference heading
+    pub max_degrees: f32,
+
+    /// Width of the zones near the limits in which rotation gradually slows down.
+    /// Zero results in a hard stop.
+    pub softness_degrees: f32,
+}
+
+impl YawLimits {
+    /// Hard limits relative to a heading of zero degrees.
+    pub fn new(min_degrees: f32, max_degrees: f32) -> Self {
+        Self {
+            reference_yaw_degrees: 0.0,
+            min_degrees,
+            max_degrees,
+            softness_degrees: 0.0,
+        }
+    }
+
+    /// Set the heading the limits are relative to.
+    pub fn reference_yaw_degrees(mut self, reference_yaw_degrees: f32) -> Self {
+        self.reference_yaw_degrees = reference_yaw_degrees;
+        self
+    }
+
+    /// Set the width of the zones near the limits in which rotation slows down.
+    pub fn softness_degrees(mut self, softness_degrees: f32) -> Self {
+        self.softness_degrees = softness_degrees;
+        self
+    }
+
+    // Yaw relative to the reference heading, in the [-180..180) range.
+    fn relative_yaw(&self, yaw_degrees: f32) -> f32 {
+        math::rem_euclid(yaw_degrees - self.reference_yaw_degrees + 180.0, 360.0) - 180.0
+    }
+
+    fn clamp(&self, yaw_degrees: f32) -> f32 {
+        self.reference_yaw_degrees
+            + math::clamp(
+                self.relative_yaw(yaw_degrees),
+                self.min_degrees,
+                self.max_degrees,
+            )
+    }
+
+    fn rotate(&self, yaw_degrees: f32, delta_degrees: f32) -> f32 {
+        let relative = self.relative_yaw(yaw_degrees);
+        let mut delta_degrees = delta_degrees;
+
+        if self.softness_degrees > 0.0 {
+            let room = if delta_degrees > 0.0 {
+                self.max_degrees - relative
+            } else {
+                relative - self.min_degrees
+            };
+            delta_degrees *= (room / self.softness_degrees).clamp(0.0, 1.0);
+        }
+
+        self.reference_yaw_degrees
+            + math::clamp(relative + delta_degrees, self.min_degrees, self.max_degrees)
+    }
 }
 
 impl Default for YawPitch {
@@ -39,6 +145,10 @@ impl YawPitch {
         Self {
             yaw_degrees: 0.0,
             pitch_degrees: 0.0,
+            min_pitch_degrees: -90.0,
+            max_pitch_degrees: 90.0,
+            pole_margin_degrees: 0.0,
+            yaw_limits: None,
         }
     }
 
@@ -64,10 +174,56 @@ impl YawPitch {
         self
     }
 
+    /// Set the limits of the pitch angle in degrees. Clamps the current pitch.
+    pub fn pitch_limits(mut self, min_pitch_degrees: f32, max_pitch_degrees: f32) -> Self {
+        assert!(
+            min_pitch_degrees <= max_pitch_degrees,
+            "the minimum pitch must not exceed the maximum pitch"
+        );
+
+        self.min_pitch_degrees = min_pitch_degrees;
+        self.max_pitch_degrees = max_pitch_degrees;
+        self.pitch_degrees = self.clamp_pitch(self.pitch_degrees);
+        self
+    }
+
+    /// Keep the pitch the specified number of degrees away from straight up and down.
+    /// Clamps the current pitch.
+    pub fn pole_margin_degrees(mut self, pole_margin_degrees: f32) -> Self {
+        self.pole_margin_degrees = pole_margin_degrees;
+        self.pitch_degrees = self.clamp_pitch(self.pitch_degrees);
+        self
+    }
+
+    /// Limit the yaw angle relative to a reference heading.
+    pub fn yaw_limits(mut self, yaw_limits: YawLimits) -> Self {
+        self.yaw_degrees = yaw_limits.clamp(self.yaw_degrees);
+        self.yaw_limits = Some(yaw_limits);
+        self
+    }
+
     /// Additively rotate by the specified angles.
+    ///
+    /// Pitch is clamped to its limits, and yaw is clamped to `yaw_limits` if present.
     pub fn rotate_yaw_pitch(&mut self, yaw_degrees: f32, pitch_degrees: f32) {
-        self.yaw_degrees = (self.yaw_degrees + yaw_degrees) % 720_f32;
-        self.pitch_degrees = (self.pitch_degrees + pitch_degrees).clamp(-90.0, 90.0);
+        self.yaw_degrees = match &self.yaw_limits {
+            Some(limits) => limits.rotate(self.yaw_degrees, yaw_degrees),
+            None => (self.yaw_degrees + yaw_degrees) % 360_f32,
+        };
+
+        self.pitch_degrees = self.clamp_pitch(self.pitch_degrees + pitch_degrees);
+    }
+
+    // Clamps to the pitch limits, and then to the pole margin, which takes precedence
+    // when the two don't overlap.
+    fn clamp_pitch(&self, pitch_degrees: f32) -> f32 {
+        let max_abs_pitch = (90.0 - self.pole_margin_degrees).max(0.0);
+        let pitch_degrees = math::clamp(
+            pitch_degrees,
+            self.min_pitch_degrees,
+            self.max_pitch_degrees,
+        );
+        math::clamp(pitch_degrees, -max_abs_pitch, max_abs_pitch)
     }
 
     /// Set the yaw and pitch angles from a quaternion.
@@ -85,6 +241,11 @@ impl YawPitch {
 
 impl<H: Handedness> RigDriver<H> for YawPitch {
     fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        // The reference heading may have moved since the last rotation.
+        if let Some(limits) = &self.yaw_limits {
+            self.yaw_degrees = limits.clamp(self.yaw_degrees);
+        }
+
         let rotation = Quat::from_euler(
             EulerRot::YXZ,
             self.yaw_degrees.to_radians(),
@@ -98,4 +259,59 @@ impl<H: Handedness> RigDriver<H> for YawPitch {
             phantom: PhantomData,
         }
     }
+
+    fn snapshot(&self) -> Option<DriverSnapshot> {
+        snapshot_by_clone(self)
+    }
+
+    fn restore(&mut self, snapshot: &(dyn Any + Send + Sync)) {
+        restore_by_clone(self, snapshot);
+    }
+}
+
+#[test]
+fn yaw_pitch_limits() {
+    let mut yaw_pitch = YawPitch::new()
+        .pitch_limits(-30.0, 60.0)
+        .yaw_limits(YawLimits::new(-45.0, 45.0).reference_yaw_degrees(90.0));
+
+    yaw_pitch.rotate_yaw_pitch(100.0, 100.0);
+    assert_eq!(yaw_pitch.yaw_degrees, 135.0);
+    assert_eq!(yaw_pitch.pitch_degrees, 60.0);
+
+    yaw_pitch.rotate_yaw_pitch(-200.0, -200.0);
+    assert_eq!(yaw_pitch.yaw_degrees, 45.0);
+    assert_eq!(yaw_pitch.pitch_degrees, -30.0);
+
+    let mut yaw_pitch = YawPitch::new()
+        .pole_margin_degrees(1.0)
+        .yaw_limits(YawLimits::new(-45.0, 45.0).softness_degrees(10.0));
+
+    yaw_pitch.rotate_yaw_pitch(40.0, 100.0);
+    assert_eq!(yaw_pitch.pitch_degrees, 89.0);
+
+    // Within the soft zone, rotation slows down, but never crosses the limit.
+    yaw_pitch.rotate_yaw_pitch(4.0, 0.0);
+    assert!(yaw_pitch.yaw_degrees > 40.0 && yaw_pitch.yaw_degrees < 44.0);
+    yaw_pitch.rotate_yaw_pitch(100.0, 0.0);
+    assert!(yaw_pitch.yaw_degrees <= 45.0);
+
+    // Limits apply immediately, and the pole margin wins when the two don't overlap.
+    let mut yaw_pitch = YawPitch::new()
+        .pitch_degrees(-45.0)
+        .pitch_limits(80.0, 90.0)
+        .pole_margin_degrees(20.0);
+    assert_eq!(yaw_pitch.pitch_degrees, 70.0);
+    yaw_pitch.rotate_yaw_pitch(0.0, 5.0);
+    assert_eq!(yaw_pitch.pitch_degrees, 70.0);
+
+    // Inverted or NaN yaw limits don't panic; the upper one wins.
+    let mut yaw_pitch =
+        YawPitch::new().yaw_limits(YawLimits::new(45.0, -45.0).softness_degrees(10.0));
+    assert_eq!(yaw_pitch.yaw_degrees, -45.0);
+    yaw_pitch.rotate_yaw_pitch(30.0, 0.0);
+    assert_eq!(yaw_pitch.yaw_degrees, -45.0);
+    yaw_pitch.yaw_limits = Some(YawLimits::new(f32::NAN, 45.0));
+    yaw_pitch.rotate_yaw_pitch(-100.0, 0.0);
+    assert_eq!(yaw_pitch.yaw_degrees, -145.0);
 }
@@ -0,0 +1,114 @@
+/// Maps linear progress in the `[0, 1]` range onto an eased curve.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+    /// No easing; the output equals the input.
+    Linear,
+
+    /// Hermite `smoothstep`; eases in and out.
+    #[default]
+    SmoothStep,
+
+    /// Cubic acceleration from zero velocity.
+    CubicIn,
+
+    /// Cubic deceleration to zero velocity.
+    CubicOut,
+
+    /// Cubic acceleration until halfway, then deceleration.
+    CubicInOut,
+
+    /// Holds the start value until the very end; no interpolation.
+    Step,
+
+    /// Cubic Bézier curve from `(0, 0)` to `(1, 1)`, with two handles, as in CSS `cubic-bezier`.
+    /// The `x` coordinates of the handles are clamped to `[0, 1]`.
+    CubicBezier { x1: f32, y1: f32, x2: f32, y2: f32 },
+
+    /// A user-provided easing function. Can't be serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    /// Evaluates the easing curve. `t` is clamped to `[0, 1]`.
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match *self {
+            Easing::Linear => t,
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => {
+                let t = 1.0 - t;
+                1.0 - t * t * t
+            }
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let t = -2.0 * t + 2.0;
+                    1.0 - t * t * t * 0.5
+                }
+            }
+            Easing::Step => {
+                if t < 1.0 {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            Easing::CubicBezier { x1, y1, x2, y2 } => {
+                let x1 = x1.clamp(0.0, 1.0);
+                let x2 = x2.clamp(0.0, 1.0);
+
+                // With the handles in `[0, 1]`, `x` is monotonic in the curve parameter,
+                // so the parameter at `t` can be found by bisection.
+                let (mut lo, mut hi) = (0.0, 1.0);
+                for _ in 0..24 {
+                    let mid = (lo + hi) * 0.5;
+                    if cubic_bezier(x1, x2, mid) < t {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+
+                cubic_bezier(y1, y2, (lo + hi) * 0.5)
+            }
+            Easing::Custom(f) => f(t),
+        }
+    }
+}
+
+// One coordinate of a cubic Bézier curve starting at 0 and ending at 1.
+fn cubic_bezier(p1: f32, p2: f32, s: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+}
+
+#[test]
+fn cubic_bezier_easing() {
+    let linear = Easing::CubicBezier {
+        x1: 0.25,
+        y1: 0.25,
+        x2: 0.75,
+        y2: 0.75,
+    };
+    let ease_in_out = Easing::CubicBezier {
+        x1: 0.42,
+        y1: 0.0,
+        x2: 0.58,
+        y2: 1.0,
+    };
+
+    for i in 0..=10 {
+        let t = i as f32 / 10.0;
+        assert!((linear.ease(t) - t).abs() < 1e-4);
+        assert!((ease_in_out.ease(t) + ease_in_out.ease(1.0 - t) - 1.0).abs() < 1e-4);
+    }
+
+    assert!(ease_in_out.ease(0.25) < 0.25);
+    assert_eq!(Easing::Step.ease(0.99), 0.0);
+    assert_eq!(Easing::Step.ease(1.0), 1.0);
+}
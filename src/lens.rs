@@ -0,0 +1,60 @@
+use crate::projection::Projection;
+
+/// Optical state of the camera, animated by the rig alongside its [`Transform`].
+///
+/// All the values are optional. Drivers which don't deal with them pass them through,
+/// and a rig without any lens drivers produces an empty lens, leaving the values to the application.
+///
+/// [`Transform`]: ../transform/struct.Transform.html
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lens {
+    /// Vertical field of view in degrees
+    pub vertical_fov_degrees: Option<f32>,
+
+    /// Distance to the near clipping plane
+    pub near: Option<f32>,
+
+    /// Distance to the far clipping plane
+    pub far: Option<f32>,
+}
+
+impl Lens {
+    /// A lens with no values set.
+    pub const EMPTY: Self = Self {
+        vertical_fov_degrees: None,
+        near: None,
+        far: None,
+    };
+
+    /// Linearly interpolates between two lenses.
+    ///
+    /// Values only present in one of the lenses can't be blended. Those of `other`
+    /// are taken as-is for any `t` above zero, so that `t == 0` always returns `self`.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        fn lerp(a: Option<f32>, b: Option<f32>, t: f32) -> Option<f32> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a + (b - a) * t),
+                (_, Some(b)) if t > 0.0 => Some(b),
+                (a, _) => a,
+            }
+        }
+
+        Self {
+            vertical_fov_degrees: lerp(self.vertical_fov_degrees, other.vertical_fov_degrees, t),
+            near: lerp(self.near, other.near, t),
+            far: lerp(self.far, other.far, t),
+        }
+    }
+
+    /// Perspective projection with the values of the lens, or `None` if the field of view
+    /// or the near plane are missing. Without a far plane, the projection is infinite.
+    pub fn perspective(&self, aspect_ratio: f32) -> Option<Projection> {
+        Some(Projection::perspective(
+            self.vertical_fov_degrees?,
+            aspect_ratio,
+            self.near?,
+            self.far.unwrap_or(f32::INFINITY),
+        ))
+    }
+}
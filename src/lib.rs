@@ -30,11 +30,33 @@
 //! [`RigDriver`]: driver/trait.RigDriver.html
 //! [`YawPitch`]: drivers/yaw_pitch/struct.YawPitch.html
 //! [`CameraRig::update`]: rig/struct.CameraRig.html#method.update
+//!
+//! # `no_std`
+//!
+//! The crate supports `no_std` environments with an allocator. Disable the default `std` feature,
+//! and enable `libm` instead, to provide the floating point math functions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("dolly requires either the `std` or the `libm` feature");
+
+extern crate alloc;
 
+#[cfg(feature = "bevy")]
+pub mod bevy;
+pub mod collision;
 pub mod driver;
 pub mod drivers;
+pub mod easing;
 pub mod handedness;
+pub mod lens;
+mod math;
 pub mod prelude;
+pub mod projection;
 pub mod rig;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod track;
 pub mod transform;
 pub mod util;
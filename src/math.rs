@@ -0,0 +1,62 @@
+//! Floating point functions which are not available in `core`.
+//!
+//! These dispatch to `std` when it's available, and to `libm` otherwise.
+
+#[cfg(feature = "std")]
+mod imp {
+    pub fn exp(x: f32) -> f32 {
+        x.exp()
+    }
+
+    pub fn sqrt(x: f32) -> f32 {
+        x.sqrt()
+    }
+
+    pub fn powf(x: f32, n: f32) -> f32 {
+        x.powf(n)
+    }
+
+    pub fn floor(x: f32) -> f32 {
+        x.floor()
+    }
+
+    pub fn sin(x: f32) -> f32 {
+        x.sin()
+    }
+
+    pub fn cos(x: f32) -> f32 {
+        x.cos()
+    }
+
+    pub fn tan(x: f32) -> f32 {
+        x.tan()
+    }
+
+    pub fn atan2(y: f32, x: f32) -> f32 {
+        y.atan2(x)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod imp {
+    pub use libm::{atan2f as atan2, cosf as cos, expf as exp, floorf as floor};
+    pub use libm::{powf, sinf as sin, sqrtf as sqrt, tanf as tan};
+}
+
+pub(crate) use imp::*;
+
+/// The non-negative remainder of `x / rhs`.
+pub(crate) fn rem_euclid(x: f32, rhs: f32) -> f32 {
+    let r = x % rhs;
+    if r < 0.0 {
+        r + rhs.abs()
+    } else {
+        r
+    }
+}
+
+/// Clamps `x` to `[min, max]`. Unlike `f32::clamp`, doesn't panic on inverted or NaN limits,
+/// which may come from user-facing settings; `max` wins over `min`, and NaN limits are ignored.
+pub(crate) fn clamp(x: f32, min: f32, max: f32) -> f32 {
+    x.max(min).min(max)
+}
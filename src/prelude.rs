@@ -1,4 +1,13 @@
-pub use crate::{drivers::*, handedness::*, rig::CameraRig};
+pub use crate::{
+    collision::{CameraCollider, PrimitiveCollider},
+    drivers::*,
+    easing::Easing,
+    handedness::*,
+    lens::Lens,
+    projection::{DepthRange, Projection},
+    rig::CameraRig,
+    util::SmoothingModel,
+};
 
 #[test]
 fn orbit_example_compile_test() {
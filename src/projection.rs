@@ -0,0 +1,205 @@
+use glam::{Mat4, Vec4};
+
+use crate::handedness::Handedness;
+
+/// The range of depth values in normalized device coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepthRange {
+    /// `[-1, 1]`, as used by OpenGL
+    NegativeOneToOne,
+
+    /// `[0, 1]`, as used by Direct3D, Vulkan, Metal, and WebGPU
+    ZeroToOne,
+}
+
+/// The shape of a [`Projection`].
+///
+/// [`Projection`]: struct.Projection.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProjectionKind {
+    Perspective {
+        vertical_fov_degrees: f32,
+        aspect_ratio: f32,
+    },
+    Orthographic {
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+    },
+}
+
+/// Produces view-to-clip space matrices, to be combined with [`Transform::view_matrix`].
+///
+/// The view space is assumed to follow the convention of the [`Handedness`] the matrix
+/// is calculated for.
+///
+/// [`Transform::view_matrix`]: ../transform/struct.Transform.html#method.view_matrix
+/// [`Handedness`]: ../handedness/trait.Handedness.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Projection {
+    pub kind: ProjectionKind,
+
+    /// Distance to the near clipping plane
+    pub near: f32,
+
+    /// Distance to the far clipping plane. Can be infinite for perspective projections.
+    pub far: f32,
+
+    /// Target depth range of the clip space
+    pub depth_range: DepthRange,
+
+    /// Map the near plane to the maximum depth, and the far plane to the minimum.
+    /// Improves depth precision with floating point depth buffers.
+    pub reverse_z: bool,
+}
+
+impl Projection {
+    /// Perspective projection with a finite far plane.
+    pub fn perspective(vertical_fov_degrees: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
+        Self {
+            kind: ProjectionKind::Perspective {
+                vertical_fov_degrees,
+                aspect_ratio,
+            },
+            near,
+            far,
+            depth_range: DepthRange::ZeroToOne,
+            reverse_z: false,
+        }
+    }
+
+    /// Perspective projection with the far plane at infinity.
+    pub fn infinite_perspective(vertical_fov_degrees: f32, aspect_ratio: f32, near: f32) -> Self {
+        Self::perspective(vertical_fov_degrees, aspect_ratio, near, f32::INFINITY)
+    }
+
+    /// Orthographic projection of the specified view-space box.
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
+        Self {
+            kind: ProjectionKind::Orthographic {
+                left,
+                right,
+                bottom,
+                top,
+            },
+            near,
+            far,
+            depth_range: DepthRange::ZeroToOne,
+            reverse_z: false,
+        }
+    }
+
+    /// Set the target depth range of the clip space.
+    pub fn depth_range(mut self, depth_range: DepthRange) -> Self {
+        self.depth_range = depth_range;
+        self
+    }
+
+    /// Map the near plane to the maximum depth, and the far plane to the minimum.
+    pub fn reverse_z(mut self, reverse_z: bool) -> Self {
+        self.reverse_z = reverse_z;
+        self
+    }
+
+    /// Returns the view-to-clip space matrix for the handedness `H`.
+    pub fn matrix<H: Handedness, M>(&self) -> M
+    where
+        M: From<mint::ColumnMatrix4<f32>>,
+    {
+        let right_handed = H::FORWARD_Z_SIGN < 0.0;
+
+        // Start with a `[0, 1]` depth range, and remap it afterwards.
+        let mut matrix = match self.kind {
+            ProjectionKind::Perspective {
+                vertical_fov_degrees,
+                aspect_ratio,
+            } => {
+                let fov = vertical_fov_degrees.to_radians();
+                match (self.far.is_finite(), right_handed) {
+                    (true, true) => Mat4::perspective_rh(fov, aspect_ratio, self.near, self.far),
+                    (true, false) => Mat4::perspective_lh(fov, aspect_ratio, self.near, self.far),
+                    (false, true) => Mat4::perspective_infinite_rh(fov, aspect_ratio, self.near),
+                    (false, false) => Mat4::perspective_infinite_lh(fov, aspect_ratio, self.near),
+                }
+            }
+            ProjectionKind::Orthographic {
+                left,
+                right,
+                bottom,
+                top,
+            } => {
+                if right_handed {
+                    Mat4::orthographic_rh(left, right, bottom, top, self.near, self.far)
+                } else {
+                    Mat4::orthographic_lh(left, right, bottom, top, self.near, self.far)
+                }
+            }
+        };
+
+        if self.reverse_z {
+            // z' = w - z
+            matrix = Mat4::from_cols(
+                Vec4::X,
+                Vec4::Y,
+                Vec4::new(0.0, 0.0, -1.0, 0.0),
+                Vec4::new(0.0, 0.0, 1.0, 1.0),
+            ) * matrix;
+        }
+
+        if self.depth_range == DepthRange::NegativeOneToOne {
+            // z' = 2z - w
+            matrix = Mat4::from_cols(
+                Vec4::X,
+                Vec4::Y,
+                Vec4::new(0.0, 0.0, 2.0, 0.0),
+                Vec4::new(0.0, 0.0, -1.0, 1.0),
+            ) * matrix;
+        }
+
+        From::from(matrix.into())
+    }
+}
+
+#[test]
+fn projection_depth_ranges() {
+    use crate::{
+        handedness::{LeftHanded, RightHanded},
+        transform::Transform,
+    };
+    use glam::{Quat, Vec3};
+
+    fn depth<H: Handedness>(projection: Projection, view_distance: f32) -> f32 {
+        let camera = Transform::<H>::from_position_rotation(
+            Vec3::new(1.0, 2.0, 3.0),
+            Quat::from_rotation_y(1.0),
+        );
+        let point = Vec3::from(camera.position) + camera.forward::<Vec3>() * view_distance;
+
+        let view: Mat4 = camera.view_matrix();
+        let clip = projection.matrix::<H, Mat4>() * view * point.extend(1.0);
+        clip.z / clip.w
+    }
+
+    fn check<H: Handedness>() {
+        let projection = Projection::perspective(60.0, 1.5, 0.1, 100.0);
+        assert!(depth::<H>(projection, 0.1).abs() < 1e-4);
+        assert!((depth::<H>(projection, 100.0) - 1.0).abs() < 1e-4);
+
+        let projection = projection
+            .depth_range(DepthRange::NegativeOneToOne)
+            .reverse_z(true);
+        assert!((depth::<H>(projection, 0.1) - 1.0).abs() < 1e-4);
+        assert!((depth::<H>(projection, 100.0) + 1.0).abs() < 1e-4);
+
+        let projection = Projection::infinite_perspective(60.0, 1.5, 0.1).reverse_z(true);
+        assert!((depth::<H>(projection, 0.1) - 1.0).abs() < 1e-4);
+        assert!(depth::<H>(projection, 1e6).abs() < 1e-4);
+
+        let projection = Projection::orthographic(-1.0, 1.0, -1.0, 1.0, 1.0, 11.0);
+        assert!((depth::<H>(projection, 6.0) - 0.5).abs() < 1e-4);
+    }
+
+    check::<RightHanded>();
+    check::<LeftHanded>();
+}
@@ -1,19 +1,188 @@
 use crate::{
-    driver::{RigDriver, RigDriverTraits},
+    driver::{DriverSnapshot, RigDriver, RigDriverTraits},
     handedness::{Handedness, RightHanded},
-    transform::Transform,
+    lens::Lens,
+    transform::{DTransform, Transform},
 };
-use core::fmt::Debug;
-use std::marker::PhantomData;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::marker::PhantomData;
+use core::{any::TypeId, fmt, fmt::Debug};
+use glam::{DVec3, Quat, Vec3};
 
 /// A chain of drivers, calculating displacements, and animating in succession.
 #[derive(Debug)]
 pub struct CameraRig<H: Handedness = RightHanded> {
-    pub drivers: Vec<Box<dyn RigDriverTraits<H>>>,
+    pub drivers: Vec<DriverSlot<H>>,
     pub final_transform: Transform<H>,
+
+    /// The lens produced by the drivers in the most recent update
+    pub final_lens: Lens,
+
+    previous_transform: Transform<H>,
+    last_delta_time_seconds: f32,
+    origin: mint::Point3<f64>,
+    fixed_timestep: Option<FixedTimestep<H>>,
     phantom: PhantomData<H>,
 }
 
+/// A driver in a [`CameraRig`], along with its optional name.
+///
+/// [`CameraRig`]: struct.CameraRig.html
+#[derive(Debug)]
+pub struct DriverSlot<H: Handedness> {
+    /// Label for looking up the driver; see [`CameraRigBuilder::with_named`]
+    ///
+    /// [`CameraRigBuilder::with_named`]: struct.CameraRigBuilder.html#method.with_named
+    pub name: Option<String>,
+
+    /// Disabled drivers pass their parent transform through unchanged.
+    ///
+    /// They are still updated, so that any smoothing keeps following the parent,
+    /// and does not jump when the driver is enabled again.
+    pub enabled: bool,
+
+    /// Influence of the driver in the `[0, 1]` range.
+    ///
+    /// The output of the driver is blended with its parent transform, interpolating
+    /// the position linearly, and the rotation spherically.
+    pub weight: f32,
+
+    pub driver: Box<dyn RigDriverTraits<H>>,
+}
+
+impl<H: Handedness> DriverSlot<H> {
+    /// Wraps an unnamed driver.
+    pub fn new(driver: impl RigDriverTraits<H>) -> Self {
+        Self {
+            name: None,
+            enabled: true,
+            weight: 1.0,
+            driver: Box::new(driver),
+        }
+    }
+
+    /// Wraps a driver with a name.
+    pub fn named(name: impl Into<String>, driver: impl RigDriverTraits<H>) -> Self {
+        Self {
+            name: Some(name.into()),
+            enabled: true,
+            weight: 1.0,
+            driver: Box::new(driver),
+        }
+    }
+
+    /// Set whether the driver affects the rig.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the influence of the driver in the `[0, 1]` range.
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Returns the driver if it's of type `T`.
+    pub fn downcast_ref<T: RigDriver<H>>(&self) -> Option<&T> {
+        self.driver.as_ref().as_any().downcast_ref::<T>()
+    }
+
+    /// Returns the driver if it's of type `T`.
+    pub fn downcast_mut<T: RigDriver<H>>(&mut self) -> Option<&mut T> {
+        self.driver.as_mut().as_any_mut().downcast_mut::<T>()
+    }
+}
+
+/// The default limit of fixed-timestep substeps in a single call to `CameraRig::update`.
+pub const DEFAULT_MAX_SUBSTEPS: u32 = 120;
+
+// State of fixed-timestep updates; see `CameraRigBuilder::fixed_timestep`.
+#[derive(Clone, Debug)]
+struct FixedTimestep<H: Handedness> {
+    step_seconds: f32,
+    max_substeps: u32,
+    accumulated_seconds: f32,
+    previous: (Transform<H>, Lens),
+    current: (Transform<H>, Lens),
+}
+
+fn assert_valid_step(step_seconds: f32) {
+    assert!(
+        step_seconds > 0.0 && step_seconds.is_finite(),
+        "the fixed timestep must be positive and finite, got {}",
+        step_seconds
+    );
+}
+
+impl<H: Handedness> FixedTimestep<H> {
+    fn new(step_seconds: f32, max_substeps: u32, transform: Transform<H>, lens: Lens) -> Self {
+        Self {
+            step_seconds,
+            max_substeps,
+            accumulated_seconds: 0.0,
+            previous: (transform, lens),
+            current: (transform, lens),
+        }
+    }
+}
+
+/// The state of a [`CameraRig`] captured by [`CameraRig::snapshot`].
+///
+/// [`CameraRig`]: struct.CameraRig.html
+/// [`CameraRig::snapshot`]: struct.CameraRig.html#method.snapshot
+#[derive(Debug)]
+pub struct RigSnapshot<H: Handedness> {
+    drivers: Vec<SlotSnapshot>,
+    final_transform: Transform<H>,
+    final_lens: Lens,
+    previous_transform: Transform<H>,
+    last_delta_time_seconds: f32,
+    origin: mint::Point3<f64>,
+    fixed_timestep: Option<FixedTimestep<H>>,
+}
+
+#[derive(Debug)]
+struct SlotSnapshot {
+    type_id: TypeId,
+    name: Option<String>,
+    enabled: bool,
+    weight: f32,
+    state: Option<DriverSnapshot>,
+}
+
+/// The reason a [`RigSnapshot`] could not be restored.
+///
+/// [`RigSnapshot`]: struct.RigSnapshot.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestoreError {
+    /// The rig has a different number of drivers than when the snapshot was taken.
+    DriverCount { expected: usize, found: usize },
+
+    /// The driver at `index` has a different type than when the snapshot was taken.
+    DriverType { index: usize },
+}
+
+impl fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DriverCount { expected, found } => write!(
+                f,
+                "the snapshot has {} drivers, but the rig has {}",
+                expected, found
+            ),
+            Self::DriverType { index } => write!(
+                f,
+                "the type of driver #{} does not match the snapshot",
+                index
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RestoreError {}
+
 // Prevents user calls to `RigDriver::update`. All updates must come from `CameraRig::update`.
 struct RigUpdateToken;
 
@@ -21,6 +190,9 @@ pub struct RigUpdateParams<'a, H: Handedness> {
     pub parent: &'a Transform<H>,
     pub delta_time_seconds: f32,
 
+    /// The lens of the parent. Drivers may modify it to change the lens of their output.
+    pub lens: &'a mut Lens,
+
     phantom: PhantomData<H>,
 
     _token: RigUpdateToken,
@@ -32,16 +204,14 @@ impl<H: Handedness> CameraRig<H> {
         self.try_driver_mut::<T>().unwrap_or_else(|| {
             panic!(
                 "No {} driver found in the CameraRig",
-                std::any::type_name::<T>()
+                core::any::type_name::<T>()
             )
         })
     }
 
     /// Returns the Some with the first driver of the matching type, or `None` if no such driver is present.
     pub fn try_driver_mut<T: RigDriver<H>>(&mut self) -> Option<&mut T> {
-        self.drivers
-            .iter_mut()
-            .find_map(|driver| driver.as_mut().as_any_mut().downcast_mut::<T>())
+        self.drivers.iter_mut().find_map(DriverSlot::downcast_mut)
     }
 
     /// Returns the first driver of the matching type. Panics if no such driver is present.
@@ -49,56 +219,551 @@ impl<H: Handedness> CameraRig<H> {
         self.try_driver::<T>().unwrap_or_else(|| {
             panic!(
                 "No {} driver found in the CameraRig",
-                std::any::type_name::<T>()
+                core::any::type_name::<T>()
             )
         })
     }
 
     /// Returns the Some with the first driver of the matching type, or `None` if no such driver is present.
     pub fn try_driver<T: RigDriver<H>>(&self) -> Option<&T> {
+        self.drivers.iter().find_map(DriverSlot::downcast_ref)
+    }
+
+    /// Returns the driver with the specified name. Panics if there is no such driver,
+    /// or if it is not of type `T`.
+    pub fn driver_by_name<T: RigDriver<H>>(&self, name: &str) -> &T {
+        self.try_driver_by_name::<T>(name).unwrap_or_else(|| {
+            panic!(
+                "No {} driver named {:?} found in the CameraRig",
+                core::any::type_name::<T>(),
+                name
+            )
+        })
+    }
+
+    /// Returns the driver with the specified name. Panics if there is no such driver,
+    /// or if it is not of type `T`.
+    pub fn driver_by_name_mut<T: RigDriver<H>>(&mut self, name: &str) -> &mut T {
+        self.try_driver_by_name_mut::<T>(name).unwrap_or_else(|| {
+            panic!(
+                "No {} driver named {:?} found in the CameraRig",
+                core::any::type_name::<T>(),
+                name
+            )
+        })
+    }
+
+    /// Returns the driver with the specified name, or `None` if there is no such driver,
+    /// or if it is not of type `T`.
+    pub fn try_driver_by_name<T: RigDriver<H>>(&self, name: &str) -> Option<&T> {
+        self.try_driver_at(self.driver_index(name)?)
+    }
+
+    /// Returns the driver with the specified name, or `None` if there is no such driver,
+    /// or if it is not of type `T`.
+    pub fn try_driver_by_name_mut<T: RigDriver<H>>(&mut self, name: &str) -> Option<&mut T> {
+        self.try_driver_at_mut(self.driver_index(name)?)
+    }
+
+    /// Returns the driver at `index` in the chain, or `None` if the index is out of bounds,
+    /// or if the driver is not of type `T`.
+    pub fn try_driver_at<T: RigDriver<H>>(&self, index: usize) -> Option<&T> {
+        self.drivers.get(index)?.downcast_ref()
+    }
+
+    /// Returns the driver at `index` in the chain, or `None` if the index is out of bounds,
+    /// or if the driver is not of type `T`.
+    pub fn try_driver_at_mut<T: RigDriver<H>>(&mut self, index: usize) -> Option<&mut T> {
+        self.drivers.get_mut(index)?.downcast_mut()
+    }
+
+    /// Returns the index in the chain of the first driver with the specified name.
+    pub fn driver_index(&self, name: &str) -> Option<usize> {
         self.drivers
             .iter()
-            .find_map(|driver| driver.as_ref().as_any().downcast_ref::<T>())
+            .position(|slot| slot.name.as_deref() == Some(name))
+    }
+
+    /// Appends a driver to the end of the chain.
+    ///
+    /// Changes to the chain take effect on the next [`update`](#method.update).
+    pub fn push_driver(&mut self, slot: DriverSlot<H>) {
+        self.drivers.push(slot);
+    }
+
+    /// Inserts a driver at `index` in the chain. Panics if `index > drivers.len()`.
+    pub fn insert_driver(&mut self, index: usize, slot: DriverSlot<H>) {
+        self.drivers.insert(index, slot);
+    }
+
+    /// Removes and returns the driver at `index` in the chain. Panics if the index is out of bounds.
+    pub fn remove_driver(&mut self, index: usize) -> DriverSlot<H> {
+        self.drivers.remove(index)
+    }
+
+    /// Removes and returns the first driver with the specified name, if any.
+    pub fn remove_driver_by_name(&mut self, name: &str) -> Option<DriverSlot<H>> {
+        let index = self.driver_index(name)?;
+        Some(self.drivers.remove(index))
+    }
+
+    /// Enables or disables the first driver with the specified name.
+    /// Returns `false` if there is no such driver.
+    ///
+    /// See [`DriverSlot::enabled`] for details.
+    ///
+    /// [`DriverSlot::enabled`]: struct.DriverSlot.html#structfield.enabled
+    pub fn set_driver_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match self.driver_index(name) {
+            Some(index) => {
+                self.drivers[index].enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the influence of the first driver with the specified name.
+    /// Returns `false` if there is no such driver.
+    ///
+    /// See [`DriverSlot::weight`] for details.
+    ///
+    /// [`DriverSlot::weight`]: struct.DriverSlot.html#structfield.weight
+    pub fn set_driver_weight(&mut self, name: &str, weight: f32) -> bool {
+        match self.driver_index(name) {
+            Some(index) => {
+                self.drivers[index].weight = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the driver at index `from` to index `to`, shifting the drivers in between.
+    /// Panics if either index is out of bounds.
+    pub fn move_driver(&mut self, from: usize, to: usize) {
+        let slot = self.drivers.remove(from);
+        self.drivers.insert(to, slot);
+    }
+
+    /// Swaps the drivers at indices `a` and `b`. Panics if either index is out of bounds.
+    pub fn swap_drivers(&mut self, a: usize, b: usize) {
+        self.drivers.swap(a, b);
+    }
+
+    /// Iterates over all the drivers of the matching type, in chain order.
+    pub fn drivers_of_type<T: RigDriver<H>>(&self) -> impl Iterator<Item = &T> {
+        self.drivers.iter().filter_map(DriverSlot::downcast_ref)
+    }
+
+    /// Iterates over all the drivers of the matching type, in chain order.
+    pub fn drivers_of_type_mut<T: RigDriver<H>>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.drivers.iter_mut().filter_map(DriverSlot::downcast_mut)
     }
 
     /// Runs all the drivers in sequence, animating the rig, and producing a final transform of the camera.
     ///
     /// Camera rigs are approximately framerate independent, so `update` can be called at any frequency.
+    /// For exact framerate independence, see [`CameraRigBuilder::fixed_timestep`].
+    ///
+    /// [`CameraRigBuilder::fixed_timestep`]: struct.CameraRigBuilder.html#method.fixed_timestep
     pub fn update(&mut self, delta_time_seconds: f32) -> Transform<H> {
+        self.previous_transform = self.final_transform;
+        self.last_delta_time_seconds = delta_time_seconds;
+
+        (self.final_transform, self.final_lens) = if let Some(mut fixed) =
+            self.fixed_timestep.take()
+        {
+            fixed.accumulated_seconds += delta_time_seconds;
+
+            let mut substeps = 0;
+            while fixed.accumulated_seconds >= fixed.step_seconds && substeps < fixed.max_substeps {
+                fixed.previous = fixed.current;
+                fixed.current = self.update_drivers(fixed.step_seconds);
+                fixed.accumulated_seconds -= fixed.step_seconds;
+                substeps += 1;
+            }
+
+            // Drop any time which could not be simulated within the substep limit.
+            fixed.accumulated_seconds = fixed.accumulated_seconds.min(fixed.step_seconds);
+
+            let t = fixed.accumulated_seconds / fixed.step_seconds;
+            let transform = fixed.previous.0.lerp(&fixed.current.0, t);
+            let lens = fixed.previous.1.lerp(&fixed.current.1, t);
+            self.fixed_timestep = Some(fixed);
+            (transform, lens)
+        } else {
+            self.update_drivers(delta_time_seconds)
+        };
+
+        self.final_transform
+    }
+
+    /// The final transform before the most recent [`update`](#method.update).
+    pub fn previous_transform(&self) -> Transform<H> {
+        self.previous_transform
+    }
+
+    /// The velocity of the camera over the most recent [`update`](#method.update), in units per second.
+    ///
+    /// Zero if the last update had no time step.
+    pub fn linear_velocity<V>(&self) -> V
+    where
+        V: From<mint::Vector3<f32>>,
+    {
+        let velocity = if self.last_delta_time_seconds > 0.0 {
+            (Vec3::from(self.final_transform.position)
+                - Vec3::from(self.previous_transform.position))
+                / self.last_delta_time_seconds
+        } else {
+            Vec3::ZERO
+        };
+
+        From::from(velocity.into())
+    }
+
+    /// The world-space angular velocity of the camera over the most recent [`update`](#method.update).
+    ///
+    /// The direction is the axis of rotation, and the length is the rate in radians per second.
+    /// Zero if the last update had no time step.
+    pub fn angular_velocity<V>(&self) -> V
+    where
+        V: From<mint::Vector3<f32>>,
+    {
+        let velocity = if self.last_delta_time_seconds > 0.0 {
+            let mut delta = Quat::from(self.final_transform.rotation)
+                * Quat::from(self.previous_transform.rotation).inverse();
+
+            // Take the shortest way around.
+            if delta.w < 0.0 {
+                delta = -delta;
+            }
+
+            delta.normalize().to_scaled_axis() / self.last_delta_time_seconds
+        } else {
+            Vec3::ZERO
+        };
+
+        From::from(velocity.into())
+    }
+
+    /// The world-space position which the rig's single-precision coordinates are relative to.
+    pub fn origin(&self) -> mint::Point3<f64> {
+        self.origin
+    }
+
+    /// Moves the floating origin of the rig, e.g. to keep it close to the camera in large worlds.
+    ///
+    /// All the coordinates of the rig are relative to its origin. Drivers shift their
+    /// stored world-space positions via [`RigDriver::rebase`], so that the camera stays in place.
+    /// Positions subsequently passed to drivers must be relative to the new origin;
+    /// see [`to_local`](#method.to_local).
+    ///
+    /// [`RigDriver::rebase`]: ../driver/trait.RigDriver.html#method.rebase
+    pub fn set_origin<P>(&mut self, origin: P)
+    where
+        P: Into<mint::Point3<f64>>,
+    {
+        let origin = origin.into();
+        let offset = (DVec3::from(self.origin) - DVec3::from(origin)).as_vec3();
+        self.origin = origin;
+        self.shift(offset);
+    }
+
+    /// Converts a world-space position to the single-precision coordinates of the rig.
+    pub fn to_local<P>(&self, position: P) -> mint::Point3<f32>
+    where
+        P: Into<mint::Point3<f64>>,
+    {
+        (DVec3::from(position.into()) - DVec3::from(self.origin))
+            .as_vec3()
+            .into()
+    }
+
+    /// The final transform of the camera in world space.
+    pub fn world_transform(&self) -> DTransform<H> {
+        DTransform::from_relative(&self.final_transform, self.origin)
+    }
+
+    /// The final transform of the camera relative to `origin`, e.g. the origin used for rendering.
+    pub fn transform_relative_to<P>(&self, origin: P) -> Transform<H>
+    where
+        P: Into<mint::Point3<f64>>,
+    {
+        let offset = (DVec3::from(self.origin) - DVec3::from(origin.into())).as_vec3();
+        let position = Vec3::from(self.final_transform.position) + offset;
+
+        Transform::from_position_rotation(position, self.final_transform.rotation)
+    }
+
+    // Moves everything in the rig by `offset`, without changing its origin.
+    pub(crate) fn shift(&mut self, offset: Vec3) {
+        for slot in self.drivers.iter_mut() {
+            slot.driver.rebase(offset.into());
+        }
+
+        let shift = |transform: &mut Transform<H>| {
+            transform.position = (Vec3::from(transform.position) + offset).into();
+        };
+
+        shift(&mut self.final_transform);
+        shift(&mut self.previous_transform);
+        if let Some(fixed) = self.fixed_timestep.as_mut() {
+            shift(&mut fixed.previous.0);
+            shift(&mut fixed.current.0);
+        }
+    }
+
+    /// Snaps the smoothing of all drivers to their current targets, e.g. after the player respawns,
+    /// and re-evaluates the rig without advancing time.
+    ///
+    /// The velocity of the camera is reset as well.
+    pub fn reset_smoothing(&mut self) -> Transform<H> {
+        for slot in self.drivers.iter_mut() {
+            slot.driver.reset_smoothing();
+        }
+
+        (self.final_transform, self.final_lens) = self.update_drivers(0.0);
+        self.previous_transform = self.final_transform;
+        if let Some(fixed) = self.fixed_timestep.as_mut() {
+            fixed.previous = (self.final_transform, self.final_lens);
+            fixed.current = fixed.previous;
+        }
+
+        self.final_transform
+    }
+
+    /// Like [`reset_smoothing`](#method.reset_smoothing), but only if the camera would jump
+    /// further than `distance_threshold` by doing so. Returns whether the camera was teleported.
+    ///
+    /// Call this after moving the targets of the rig, e.g. when loading a level.
+    /// Drivers which don't support snapshots (see [`RigDriver::snapshot`]) are reset regardless.
+    ///
+    /// [`RigDriver::snapshot`]: ../driver/trait.RigDriver.html#method.snapshot
+    pub fn teleport(&mut self, distance_threshold: f32) -> bool {
+        let snapshot = self.snapshot();
+        let position = Vec3::from(self.final_transform.position);
+        let snapped = Vec3::from(self.reset_smoothing().position);
+
+        if position.distance(snapped) > distance_threshold {
+            true
+        } else {
+            // The snapshot was taken from this very rig, so it always matches.
+            let _ = self.restore(&snapshot);
+            false
+        }
+    }
+
+    /// Captures the state of the rig, including the parameters and smoothing state of its drivers.
+    ///
+    /// Drivers which don't support snapshots (see [`RigDriver::snapshot`]) are not captured,
+    /// and will be left as they are by [`restore`](#method.restore).
+    ///
+    /// [`RigDriver::snapshot`]: ../driver/trait.RigDriver.html#method.snapshot
+    pub fn snapshot(&self) -> RigSnapshot<H> {
+        RigSnapshot {
+            drivers: self
+                .drivers
+                .iter()
+                .map(|slot| SlotSnapshot {
+                    type_id: slot.driver.as_any().type_id(),
+                    name: slot.name.clone(),
+                    enabled: slot.enabled,
+                    weight: slot.weight,
+                    state: slot.driver.snapshot(),
+                })
+                .collect(),
+            final_transform: self.final_transform,
+            final_lens: self.final_lens,
+            previous_transform: self.previous_transform,
+            last_delta_time_seconds: self.last_delta_time_seconds,
+            origin: self.origin,
+            fixed_timestep: self.fixed_timestep.clone(),
+        }
+    }
+
+    /// Restores the state captured by [`snapshot`](#method.snapshot).
+    ///
+    /// The chain of drivers must have the same types as when the snapshot was taken,
+    /// otherwise nothing is restored, and an error is returned.
+    pub fn restore(&mut self, snapshot: &RigSnapshot<H>) -> Result<(), RestoreError> {
+        if snapshot.drivers.len() != self.drivers.len() {
+            return Err(RestoreError::DriverCount {
+                expected: snapshot.drivers.len(),
+                found: self.drivers.len(),
+            });
+        }
+
+        for (index, (slot, saved)) in self.drivers.iter().zip(&snapshot.drivers).enumerate() {
+            if slot.driver.as_any().type_id() != saved.type_id {
+                return Err(RestoreError::DriverType { index });
+            }
+        }
+
+        for (slot, saved) in self.drivers.iter_mut().zip(&snapshot.drivers) {
+            slot.name = saved.name.clone();
+            slot.enabled = saved.enabled;
+            slot.weight = saved.weight;
+            if let Some(state) = &saved.state {
+                slot.driver.restore(state.as_ref());
+            }
+        }
+
+        self.final_transform = snapshot.final_transform;
+        self.final_lens = snapshot.final_lens;
+        self.previous_transform = snapshot.previous_transform;
+        self.last_delta_time_seconds = snapshot.last_delta_time_seconds;
+        self.origin = snapshot.origin;
+        self.fixed_timestep = snapshot.fixed_timestep.clone();
+
+        Ok(())
+    }
+
+    /// Switches between fixed-timestep updates, and running drivers once per `update`.
+    ///
+    /// See [`CameraRigBuilder::fixed_timestep`] for details.
+    ///
+    /// [`CameraRigBuilder::fixed_timestep`]: struct.CameraRigBuilder.html#method.fixed_timestep
+    pub fn set_fixed_timestep(&mut self, step_seconds: Option<f32>) {
+        if let Some(step_seconds) = step_seconds {
+            assert_valid_step(step_seconds);
+        }
+
+        let max_substeps = self.max_substeps();
+
+        self.fixed_timestep = step_seconds.map(|step_seconds| {
+            FixedTimestep::new(
+                step_seconds,
+                max_substeps,
+                self.final_transform,
+                self.final_lens,
+            )
+        });
+    }
+
+    /// The duration of fixed-timestep substeps, if enabled.
+    pub fn fixed_timestep(&self) -> Option<f32> {
+        self.fixed_timestep.as_ref().map(|fixed| fixed.step_seconds)
+    }
+
+    /// The limit of fixed-timestep substeps in a single `update`.
+    ///
+    /// See [`CameraRigBuilder::max_substeps`].
+    ///
+    /// [`CameraRigBuilder::max_substeps`]: struct.CameraRigBuilder.html#method.max_substeps
+    pub fn max_substeps(&self) -> u32 {
+        self.fixed_timestep
+            .as_ref()
+            .map_or(DEFAULT_MAX_SUBSTEPS, |fixed| fixed.max_substeps)
+    }
+
+    fn update_drivers(&mut self, delta_time_seconds: f32) -> (Transform<H>, Lens) {
         let mut parent_transform = Transform::IDENTITY;
+        let mut parent_lens = Lens::EMPTY;
 
-        for driver in self.drivers.iter_mut() {
-            let transform = driver.update(RigUpdateParams {
+        for slot in self.drivers.iter_mut() {
+            let mut lens = parent_lens;
+            let transform = slot.driver.update(RigUpdateParams {
                 parent: &parent_transform,
                 delta_time_seconds,
+                lens: &mut lens,
                 phantom: PhantomData,
                 _token: RigUpdateToken,
             });
 
-            parent_transform = transform;
+            if slot.enabled {
+                if slot.weight >= 1.0 {
+                    parent_transform = transform;
+                    parent_lens = lens;
+                } else {
+                    let weight = slot.weight.max(0.0);
+                    parent_transform = parent_transform.lerp(&transform, weight);
+                    parent_lens = parent_lens.lerp(&lens, weight);
+                }
+            }
         }
 
-        self.final_transform = parent_transform;
-        self.final_transform
+        (parent_transform, parent_lens)
     }
 
     /// Use this to make a new rig
     pub fn builder() -> CameraRigBuilder<H> {
         CameraRigBuilder {
             drivers: Default::default(),
+            fixed_timestep: None,
+            max_substeps: DEFAULT_MAX_SUBSTEPS,
+            origin: DVec3::ZERO.into(),
             phantom: PhantomData,
         }
     }
 }
 
 pub struct CameraRigBuilder<H: Handedness> {
-    drivers: Vec<Box<dyn RigDriverTraits<H>>>,
+    drivers: Vec<DriverSlot<H>>,
+    fixed_timestep: Option<f32>,
+    max_substeps: u32,
+    origin: mint::Point3<f64>,
     phantom: PhantomData<H>,
 }
 
 impl<H: Handedness> CameraRigBuilder<H> {
     pub fn with(mut self, driver: impl RigDriverTraits<H>) -> Self {
-        self.drivers.push(Box::new(driver));
+        self.drivers.push(DriverSlot::new(driver));
+        self
+    }
+
+    /// Add a driver with a name, which can be used to look it up with
+    /// [`CameraRig::driver_by_name`], regardless of its position in the chain.
+    ///
+    /// [`CameraRig::driver_by_name`]: struct.CameraRig.html#method.driver_by_name
+    pub fn with_named(mut self, name: impl Into<String>, driver: impl RigDriverTraits<H>) -> Self {
+        self.drivers.push(DriverSlot::named(name, driver));
+        self
+    }
+
+    /// Add a driver along with its slot settings, e.g. to start out disabled.
+    pub fn with_slot(mut self, slot: DriverSlot<H>) -> Self {
+        self.drivers.push(slot);
+        self
+    }
+
+    /// Run the drivers in fixed increments of `step_seconds`, rather than once per [`CameraRig::update`].
+    ///
+    /// Time passed to `update` is accumulated, and simulated in as many steps as fit in it.
+    /// The final transform is interpolated between the results of the last two steps,
+    /// which delays it by up to one step. In exchange, rigs behave identically
+    /// regardless of the frame rate, and of any hitches.
+    ///
+    /// Panics unless `step_seconds` is positive and finite.
+    ///
+    /// [`CameraRig::update`]: struct.CameraRig.html#method.update
+    pub fn fixed_timestep(mut self, step_seconds: f32) -> Self {
+        assert_valid_step(step_seconds);
+        self.fixed_timestep = Some(step_seconds);
+        self
+    }
+
+    /// Limit the number of fixed-timestep substeps in a single `update`. Any excess time is dropped.
+    ///
+    /// Defaults to [`DEFAULT_MAX_SUBSTEPS`]. Panics if `max_substeps` is zero.
+    ///
+    /// [`DEFAULT_MAX_SUBSTEPS`]: constant.DEFAULT_MAX_SUBSTEPS.html
+    pub fn max_substeps(mut self, max_substeps: u32) -> Self {
+        assert!(max_substeps > 0, "at least one substep must be allowed");
+        self.max_substeps = max_substeps;
+        self
+    }
+
+    /// Set the world-space position which the coordinates of the rig are relative to.
+    ///
+    /// See [`CameraRig::set_origin`].
+    ///
+    /// [`CameraRig::set_origin`]: struct.CameraRig.html#method.set_origin
+    pub fn origin<P>(mut self, origin: P) -> Self
+    where
+        P: Into<mint::Point3<f64>>,
+    {
+        self.origin = origin.into();
         self
     }
 
@@ -107,11 +772,321 @@ impl<H: Handedness> CameraRigBuilder<H> {
             drivers: self.drivers,
             // Initialize with a dummy identity transform. Will be overridden in a moment.
             final_transform: Transform::IDENTITY,
+            final_lens: Lens::EMPTY,
+            previous_transform: Transform::IDENTITY,
+            last_delta_time_seconds: 0.0,
+            origin: self.origin,
+            fixed_timestep: None,
             phantom: PhantomData,
         };
 
         // Update once to find the final transform
         rig.update(0.0);
+        // Don't report motion from the dummy transform.
+        rig.previous_transform = rig.final_transform;
+
+        rig.fixed_timestep = self.fixed_timestep.map(|step_seconds| {
+            FixedTimestep::new(
+                step_seconds,
+                self.max_substeps,
+                rig.final_transform,
+                rig.final_lens,
+            )
+        });
+
         rig
     }
 }
+
+#[test]
+fn fixed_timestep_is_framerate_independent() {
+    use crate::drivers::{Position, Smooth};
+    use glam::Vec3;
+
+    // 47 Hz doesn't divide the step evenly, leaving time in the accumulator to interpolate.
+    let positions = [30, 47, 60, 240].map(|frequency| {
+        let mut camera: CameraRig = CameraRig::builder()
+            .with(Position::new(Vec3::ZERO))
+            .with(Smooth::new_position(1.25).predictive(true))
+            .with(Smooth::new_position(2.5))
+            .fixed_timestep(1.0 / 240.0)
+            .build();
+
+        camera.driver_mut::<Position>().position = (Vec3::X * 10.0).into();
+
+        for _ in 0..frequency {
+            camera.update(1.0 / frequency as f32);
+        }
+
+        // End between two steps.
+        camera.update(0.4 / 240.0);
+
+        Vec3::from(camera.final_transform.position)
+    });
+
+    for position in &positions[..3] {
+        assert!(position.distance(positions[3]) < 1e-3);
+    }
+}
+
+#[test]
+#[should_panic(expected = "at least one substep")]
+fn zero_max_substeps_panics() {
+    let _: CameraRig = CameraRig::builder()
+        .fixed_timestep(1.0 / 60.0)
+        .max_substeps(0)
+        .build();
+}
+
+#[test]
+fn rig_velocity() {
+    use crate::drivers::{Position, Rotation};
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Position::new(Vec3::ZERO))
+        .with(Rotation::new(Quat::IDENTITY))
+        .build();
+
+    assert_eq!(camera.linear_velocity::<Vec3>(), Vec3::ZERO);
+
+    // A freshly built rig starts at rest where it is, rather than at the identity transform.
+    let built: CameraRig = CameraRig::builder()
+        .with(Position::new(Vec3::Y))
+        .with(Rotation::new(Quat::from_rotation_x(0.5)))
+        .build();
+    let previous = built.previous_transform();
+    assert_eq!(previous.position, built.final_transform.position);
+    assert_eq!(previous.rotation, built.final_transform.rotation);
+
+    camera.driver_mut::<Position>().position = Vec3::new(1.0, 0.0, 0.0).into();
+    camera.driver_mut::<Rotation>().rotation = Quat::from_rotation_y(0.1).into();
+    camera.update(0.5);
+
+    let previous: Vec3 = camera.previous_transform().position.into();
+    assert_eq!(previous, Vec3::ZERO);
+    assert!(camera
+        .linear_velocity::<Vec3>()
+        .abs_diff_eq(Vec3::new(2.0, 0.0, 0.0), 1e-5));
+    assert!(camera
+        .angular_velocity::<Vec3>()
+        .abs_diff_eq(Vec3::new(0.0, 0.2, 0.0), 1e-5));
+}
+
+#[test]
+fn named_drivers() {
+    use crate::drivers::{Position, Smooth};
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Position::new(Vec3::ZERO))
+        .with_named("pos_smooth", Smooth::new_position(1.0))
+        .with(Smooth::new_rotation(2.0))
+        .build();
+
+    assert_eq!(camera.driver_index("pos_smooth"), Some(1));
+    assert_eq!(
+        camera
+            .driver_by_name::<Smooth>("pos_smooth")
+            .position_smoothness,
+        1.0
+    );
+    assert!(camera
+        .try_driver_by_name::<Position>("pos_smooth")
+        .is_none());
+    assert!(camera.try_driver_by_name::<Smooth>("missing").is_none());
+
+    assert_eq!(camera.drivers_of_type::<Smooth>().count(), 2);
+    camera
+        .drivers_of_type_mut::<Smooth>()
+        .for_each(|smooth| smooth.rotation_smoothness = 0.5);
+    assert_eq!(
+        camera
+            .try_driver_at::<Smooth>(2)
+            .unwrap()
+            .rotation_smoothness,
+        0.5
+    );
+}
+
+#[test]
+fn driver_chain_editing() {
+    use crate::drivers::{Arm, Position, Smooth};
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Position::new(Vec3::ZERO))
+        .with_slot(DriverSlot::named("aim", Arm::new(Vec3::X)).enabled(false))
+        .build();
+
+    assert_eq!(camera.final_transform.position, Vec3::ZERO.into());
+
+    camera.push_driver(DriverSlot::named("smooth", Smooth::new_position(1.0)));
+    camera.update(0.0);
+
+    // A disabled smoother keeps following its parent, so it doesn't jump when re-enabled.
+    assert!(camera.set_driver_enabled("smooth", false));
+    assert!(camera.set_driver_enabled("aim", true));
+    assert_eq!(camera.update(0.0).position, Vec3::X.into());
+    for _ in 0..10 {
+        camera.update(0.1);
+    }
+    camera.set_driver_enabled("smooth", true);
+    let position: Vec3 = camera.update(1.0 / 60.0).position.into();
+    assert!(position.distance(Vec3::X) < 1e-3);
+
+    camera.move_driver(2, 0);
+    assert_eq!(camera.driver_index("smooth"), Some(0));
+    camera.swap_drivers(1, 2);
+    assert_eq!(camera.driver_index("aim"), Some(1));
+
+    assert!(camera.remove_driver_by_name("aim").is_some());
+    assert!(camera.remove_driver_by_name("aim").is_none());
+    camera.insert_driver(0, DriverSlot::new(Position::new(Vec3::Y)));
+    assert_eq!(camera.drivers.len(), 3);
+}
+
+#[test]
+fn driver_weights() {
+    use crate::drivers::{Arm, Position, Rotation};
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Position::new(Vec3::ZERO))
+        .with_slot(DriverSlot::named("turn", Rotation::new(Quat::from_rotation_y(1.0))).weight(0.5))
+        .with_slot(DriverSlot::named("shoulder", Arm::new(Vec3::X)).weight(0.25))
+        .build();
+
+    let rotation: Quat = camera.final_transform.rotation.into();
+    assert!(rotation.angle_between(Quat::from_rotation_y(0.5)) < 1e-4);
+    let position: Vec3 = camera.final_transform.position.into();
+    assert!(position.distance(rotation * Vec3::X * 0.25) < 1e-5);
+
+    assert!(camera.set_driver_weight("shoulder", 0.0));
+    let position: Vec3 = camera.update(0.0).position.into();
+    assert!(position.length() < 1e-5);
+}
+
+#[test]
+#[should_panic(expected = "the fixed timestep must be positive")]
+fn zero_fixed_timestep_panics() {
+    let _: CameraRig = CameraRig::builder().fixed_timestep(0.0).build();
+}
+
+#[test]
+fn zero_weight_lens_is_ignored() {
+    use crate::drivers::Fov;
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with_slot(DriverSlot::named("zoom", Fov::new(90.0)).weight(0.0))
+        .build();
+    assert_eq!(camera.final_lens, Lens::EMPTY);
+
+    assert!(camera.set_driver_weight("zoom", 0.5));
+    camera.update(0.0);
+    assert_eq!(camera.final_lens.vertical_fov_degrees, Some(90.0));
+}
+
+#[test]
+fn floating_origin() {
+    use crate::drivers::{LookAt, Position, Smooth};
+    use glam::DVec3;
+
+    let origin = DVec3::new(1e9, 0.0, -2e9);
+    let mut camera: CameraRig = CameraRig::builder()
+        .origin(origin)
+        .with(Position::new(Vec3::new(1.0, 2.0, 3.0)))
+        .with(Smooth::new_position(1.0))
+        .with(LookAt::new(Vec3::ZERO))
+        .build();
+
+    let world = DVec3::from(camera.world_transform().position);
+    assert_eq!(world, origin + DVec3::new(1.0, 2.0, 3.0));
+
+    let rotation: Quat = camera.final_transform.rotation.into();
+    camera.set_origin(origin + DVec3::new(100.0, 0.0, 0.0));
+
+    // Nothing moves in the world, including the smoothed position and the look-at target.
+    camera.update(1.0 / 60.0);
+    assert_eq!(DVec3::from(camera.world_transform().position), world);
+    let position: Vec3 = camera.final_transform.position.into();
+    assert!(position.distance(Vec3::new(-99.0, 2.0, 3.0)) < 1e-4);
+    assert!(Quat::from(camera.final_transform.rotation).angle_between(rotation) < 1e-4);
+
+    let relative: Vec3 = camera
+        .transform_relative_to(origin + DVec3::new(1.0, 0.0, 0.0))
+        .position
+        .into();
+    assert!(relative.distance(Vec3::new(0.0, 2.0, 3.0)) < 1e-4);
+}
+
+#[test]
+fn snapshot_restores_smoothing() {
+    use crate::drivers::{Position, Smooth, YawPitch};
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Position::new(Vec3::ZERO))
+        .with(YawPitch::new())
+        .with(Smooth::new_position_rotation(1.0, 1.0))
+        .build();
+
+    camera.driver_mut::<Position>().position = Vec3::X.into();
+    camera.driver_mut::<YawPitch>().rotate_yaw_pitch(90.0, 0.0);
+    camera.update(0.1);
+
+    let snapshot = camera.snapshot();
+    let run = |camera: &mut CameraRig| {
+        for _ in 0..10 {
+            camera.update(0.1);
+        }
+        camera.final_transform
+    };
+
+    let expected = run(&mut camera);
+    camera.driver_mut::<Position>().position = Vec3::Y.into();
+    camera.restore(&snapshot).unwrap();
+    let actual = run(&mut camera);
+
+    assert_eq!(actual.position, expected.position);
+    assert_eq!(actual.rotation, expected.rotation);
+
+    camera.push_driver(DriverSlot::new(Position::new(Vec3::ZERO)));
+    assert_eq!(
+        camera.restore(&snapshot),
+        Err(RestoreError::DriverCount {
+            expected: 3,
+            found: 4
+        })
+    );
+}
+
+#[test]
+fn teleport_snaps_smoothing() {
+    use crate::drivers::{LookAt, Position, Smooth};
+
+    let mut camera: CameraRig = CameraRig::builder()
+        .with(Position::new(Vec3::ZERO))
+        .with(Smooth::new_position(1.0))
+        .with(LookAt::new(-Vec3::Z).tracking_smoothness(1.0))
+        .build();
+
+    // Small moves stay smooth.
+    camera.driver_mut::<Position>().position = Vec3::X.into();
+    assert!(!camera.teleport(10.0));
+    assert_eq!(camera.update(0.0).position, Vec3::ZERO.into());
+
+    camera.driver_mut::<Position>().position = Vec3::new(100.0, 0.0, 0.0).into();
+    camera.driver_mut::<LookAt>().target = Vec3::new(100.0, 0.0, -1.0).into();
+    assert!(camera.teleport(10.0));
+
+    let forward: Vec3 = camera.final_transform.forward();
+    assert_eq!(
+        camera.final_transform.position,
+        camera.previous_transform().position
+    );
+    assert!(
+        Vec3::from(camera.final_transform.position).distance(Vec3::new(100.0, 0.0, 0.0)) < 1e-5
+    );
+    assert!(forward.distance(-Vec3::Z) < 1e-5);
+
+    // Explicit resets snap unconditionally.
+    camera.driver_mut::<Position>().position = Vec3::new(101.0, 0.0, 0.0).into();
+    let position: Vec3 = camera.reset_smoothing().position.into();
+    assert!(position.distance(Vec3::new(101.0, 0.0, 0.0)) < 1e-5);
+}
@@ -0,0 +1,534 @@
+//! Serialization of whole camera rigs.
+//!
+//! Individual drivers implement `Serialize` and `Deserialize` when the `serde` feature is enabled.
+//! A [`CameraRig`] holds drivers as trait objects however, so its driver types need to be
+//! registered with a [`DriverRegistry`], which stores every driver under a string tag.
+//!
+//! Along with the drivers, the floating origin and fixed-timestep settings of the rig are saved.
+//! Smoothing state is not persisted; deserialized rigs start out settled at their targets.
+//!
+//! [`CameraRig`]: ../rig/struct.CameraRig.html
+//! [`DriverRegistry`]: struct.DriverRegistry.html
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::{any::TypeId, fmt};
+
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::{self, SerializeSeq, SerializeStruct},
+    Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    driver::RigDriverTraits,
+    drivers::{
+        Arm, DollyZoom, Fov, FreeRotation, HeadBob, Keyframes, LockPosition, LookAt, Orbit,
+        Playback, Position, Rotation, Shake, Smooth, SplinePath, YawPitch,
+    },
+    handedness::Handedness,
+    rig::{CameraRig, DriverSlot},
+};
+
+type SerializeFn<H> =
+    for<'a> fn(&'a dyn RigDriverTraits<H>) -> Option<&'a dyn erased_serde::Serialize>;
+type DeserializeFn<H> = fn(
+    &mut dyn erased_serde::Deserializer,
+) -> Result<Box<dyn RigDriverTraits<H>>, erased_serde::Error>;
+
+struct RegistryEntry<H: Handedness> {
+    tag: &'static str,
+    type_id: TypeId,
+    serialize: SerializeFn<H>,
+    deserialize: DeserializeFn<H>,
+}
+
+/// Maps driver types to string tags, allowing [`CameraRig`]s to be serialized and deserialized.
+///
+/// [`CameraRig`]: ../rig/struct.CameraRig.html
+pub struct DriverRegistry<H: Handedness> {
+    entries: Vec<RegistryEntry<H>>,
+}
+
+impl<H: Handedness + Send + Sync> Default for DriverRegistry<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Handedness> fmt::Debug for DriverRegistry<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|entry| entry.tag))
+            .finish()
+    }
+}
+
+// `Playback<H>` is only a driver for thread-safe handedness types.
+impl<H: Handedness + Send + Sync> DriverRegistry<H> {
+    /// Creates a registry with all the built-in serializable drivers, tagged by their type names.
+    pub fn new() -> Self {
+        Self::empty()
+            .with::<Arm>("Arm")
+            .with::<DollyZoom>("DollyZoom")
+            .with::<Fov>("Fov")
+            .with::<FreeRotation>("FreeRotation")
+            .with::<HeadBob>("HeadBob")
+            .with::<Keyframes>("Keyframes")
+            .with::<LockPosition>("LockPosition")
+            .with::<LookAt>("LookAt")
+            .with::<Orbit>("Orbit")
+            .with::<Playback<H>>("Playback")
+            .with::<Position>("Position")
+            .with::<Rotation>("Rotation")
+            .with::<Shake>("Shake")
+            .with::<Smooth>("Smooth")
+            .with::<SplinePath>("SplinePath")
+            .with::<YawPitch>("YawPitch")
+    }
+}
+
+impl<H: Handedness> DriverRegistry<H> {
+    /// Creates a registry without any drivers.
+    pub fn empty() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Registers the driver type `T` under `tag`. Replaces any previous registration of `T` or `tag`.
+    pub fn with<T>(mut self, tag: &'static str) -> Self
+    where
+        T: RigDriverTraits<H> + Serialize + DeserializeOwned,
+    {
+        self.entries
+            .retain(|entry| entry.tag != tag && entry.type_id != TypeId::of::<T>());
+        self.entries.push(RegistryEntry {
+            tag,
+            type_id: TypeId::of::<T>(),
+            serialize: serialize_driver::<H, T>,
+            deserialize: deserialize_driver::<H, T>,
+        });
+        self
+    }
+
+    /// Serializes the drivers of `rig`, along with its origin and fixed-timestep settings.
+    /// Fails if any of the drivers are not registered.
+    pub fn serialize_rig<S>(&self, rig: &CameraRig<H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("CameraRig", RIG_FIELDS.len())?;
+        state.serialize_field(
+            "drivers",
+            &SerializeDrivers {
+                registry: self,
+                rig,
+            },
+        )?;
+        state.serialize_field("origin", &rig.origin())?;
+        state.serialize_field("fixed_timestep", &rig.fixed_timestep())?;
+        state.serialize_field("max_substeps", &rig.max_substeps())?;
+        state.end()
+    }
+
+    /// Deserializes a rig previously written by [`serialize_rig`].
+    ///
+    /// [`serialize_rig`]: struct.DriverRegistry.html#method.serialize_rig
+    pub fn deserialize_rig<'de, D>(&self, deserializer: D) -> Result<CameraRig<H>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("CameraRig", RIG_FIELDS, RigVisitor { registry: self })
+    }
+
+    fn entry_for_driver(&self, driver: &dyn RigDriverTraits<H>) -> Option<&RegistryEntry<H>> {
+        let type_id = driver.as_any().type_id();
+        self.entries.iter().find(|entry| entry.type_id == type_id)
+    }
+
+    fn entry_for_tag(&self, tag: &str) -> Option<&RegistryEntry<H>> {
+        self.entries.iter().find(|entry| entry.tag == tag)
+    }
+}
+
+fn serialize_driver<H, T>(driver: &dyn RigDriverTraits<H>) -> Option<&dyn erased_serde::Serialize>
+where
+    H: Handedness,
+    T: RigDriverTraits<H> + Serialize,
+{
+    driver
+        .as_any()
+        .downcast_ref::<T>()
+        .map(|driver| driver as &dyn erased_serde::Serialize)
+}
+
+fn deserialize_driver<H, T>(
+    deserializer: &mut dyn erased_serde::Deserializer,
+) -> Result<Box<dyn RigDriverTraits<H>>, erased_serde::Error>
+where
+    H: Handedness,
+    T: RigDriverTraits<H> + DeserializeOwned,
+{
+    Ok(Box::new(erased_serde::deserialize::<T>(deserializer)?))
+}
+
+struct SerializeDrivers<'a, H: Handedness> {
+    registry: &'a DriverRegistry<H>,
+    rig: &'a CameraRig<H>,
+}
+
+impl<'a, H: Handedness> Serialize for SerializeDrivers<'a, H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.rig.drivers.len()))?;
+
+        for (i, slot) in self.rig.drivers.iter().enumerate() {
+            let driver = slot.driver.as_ref();
+            let entry = self.registry.entry_for_driver(driver).ok_or_else(|| {
+                ser::Error::custom(format!(
+                    "driver #{} ({:?}) is not registered with the DriverRegistry",
+                    i, driver
+                ))
+            })?;
+
+            seq.serialize_element(&SerializeDriver {
+                tag: entry.tag,
+                name: slot.name.as_deref(),
+                enabled: slot.enabled,
+                weight: slot.weight,
+                driver: (entry.serialize)(driver).expect("registry type mismatch"),
+            })?;
+        }
+
+        seq.end()
+    }
+}
+
+struct SerializeDriver<'a> {
+    tag: &'static str,
+    name: Option<&'a str>,
+    enabled: bool,
+    weight: f32,
+    driver: &'a dyn erased_serde::Serialize,
+}
+
+impl<'a> Serialize for SerializeDriver<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Driver", 5)?;
+        state.serialize_field("type", self.tag)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("enabled", &self.enabled)?;
+        state.serialize_field("weight", &self.weight)?;
+        state.serialize_field("driver", self.driver)?;
+        state.end()
+    }
+}
+
+const RIG_FIELDS: &[&str] = &["drivers", "origin", "fixed_timestep", "max_substeps"];
+
+struct RigVisitor<'a, H: Handedness> {
+    registry: &'a DriverRegistry<H>,
+}
+
+// Settings which rigs saved before they were added don't have, and which take their defaults.
+struct RigSettings {
+    origin: Option<mint::Point3<f64>>,
+    fixed_timestep: Option<f32>,
+    max_substeps: Option<u32>,
+}
+
+impl RigSettings {
+    fn build<H: Handedness, E: de::Error>(
+        self,
+        drivers: Vec<DriverSlot<H>>,
+    ) -> Result<CameraRig<H>, E> {
+        let mut builder = CameraRig::builder();
+        for slot in drivers {
+            builder = builder.with_slot(slot);
+        }
+        if let Some(origin) = self.origin {
+            builder = builder.origin(origin);
+        }
+        if let Some(step_seconds) = self.fixed_timestep {
+            if !(step_seconds > 0.0 && step_seconds.is_finite()) {
+                return Err(E::custom(format!(
+                    "the fixed timestep must be positive and finite, got {}",
+                    step_seconds
+                )));
+            }
+            builder = builder.fixed_timestep(step_seconds);
+        }
+        if let Some(max_substeps) = self.max_substeps {
+            if max_substeps == 0 {
+                return Err(E::custom("at least one substep must be allowed"));
+            }
+            builder = builder.max_substeps(max_substeps);
+        }
+        Ok(builder.build())
+    }
+}
+
+impl<'a, 'de, H: Handedness> Visitor<'de> for RigVisitor<'a, H> {
+    type Value = CameraRig<H>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a camera rig")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let drivers = seq
+            .next_element_seed(DriversSeed {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let settings = RigSettings {
+            origin: seq.next_element()?,
+            fixed_timestep: seq.next_element::<Option<f32>>()?.flatten(),
+            max_substeps: seq.next_element()?,
+        };
+
+        settings.build(drivers)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut drivers = None;
+        let mut settings = RigSettings {
+            origin: None,
+            fixed_timestep: None,
+            max_substeps: None,
+        };
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "drivers" => {
+                    drivers = Some(map.next_value_seed(DriversSeed {
+                        registry: self.registry,
+                    })?);
+                }
+                "origin" => settings.origin = Some(map.next_value()?),
+                "fixed_timestep" => settings.fixed_timestep = map.next_value()?,
+                "max_substeps" => settings.max_substeps = Some(map.next_value()?),
+                _ => return Err(de::Error::unknown_field(&key, RIG_FIELDS)),
+            }
+        }
+
+        let drivers = drivers.ok_or_else(|| de::Error::missing_field("drivers"))?;
+        settings.build(drivers)
+    }
+}
+
+struct DriversSeed<'a, H: Handedness> {
+    registry: &'a DriverRegistry<H>,
+}
+
+impl<'a, 'de, H: Handedness> DeserializeSeed<'de> for DriversSeed<'a, H> {
+    type Value = Vec<DriverSlot<H>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de, H: Handedness> Visitor<'de> for DriversSeed<'a, H> {
+    type Value = Vec<DriverSlot<H>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of tagged drivers")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut drivers = Vec::new();
+
+        while let Some(driver) = seq.next_element_seed(DriverSeed {
+            registry: self.registry,
+        })? {
+            drivers.push(driver);
+        }
+
+        Ok(drivers)
+    }
+}
+
+struct DriverSeed<'a, H: Handedness> {
+    registry: &'a DriverRegistry<H>,
+}
+
+const DRIVER_FIELDS: &[&str] = &["type", "name", "enabled", "weight", "driver"];
+
+impl<'a, 'de, H: Handedness> DeserializeSeed<'de> for DriverSeed<'a, H> {
+    type Value = DriverSlot<H>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("Driver", DRIVER_FIELDS, self)
+    }
+}
+
+impl<'a, 'de, H: Handedness> Visitor<'de> for DriverSeed<'a, H> {
+    type Value = DriverSlot<H>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a tagged driver")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let tag: String = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let entry = self
+            .registry
+            .entry_for_tag(&tag)
+            .ok_or_else(|| de::Error::custom(format!("unknown driver type {:?}", tag)))?;
+
+        let name: Option<String> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let enabled: bool = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let weight: f32 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+        let driver = seq
+            .next_element_seed(ErasedDriverSeed(entry.deserialize))?
+            .ok_or_else(|| de::Error::invalid_length(4, &self))?;
+
+        Ok(DriverSlot {
+            name,
+            enabled,
+            weight,
+            driver,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entry = None;
+        let mut name = None;
+        let mut enabled = true;
+        let mut weight = 1.0;
+        let mut driver = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "type" => {
+                    let tag: String = map.next_value()?;
+                    entry = Some(self.registry.entry_for_tag(&tag).ok_or_else(|| {
+                        de::Error::custom(format!("unknown driver type {:?}", tag))
+                    })?);
+                }
+                "name" => name = map.next_value()?,
+                "enabled" => enabled = map.next_value()?,
+                "weight" => weight = map.next_value()?,
+                "driver" => {
+                    // The tag is needed to pick the type to deserialize.
+                    let entry: &RegistryEntry<H> = entry
+                        .ok_or_else(|| de::Error::custom("driver `type` must precede its data"))?;
+                    driver = Some(map.next_value_seed(ErasedDriverSeed(entry.deserialize))?);
+                }
+                _ => return Err(de::Error::unknown_field(&key, DRIVER_FIELDS)),
+            }
+        }
+
+        let driver = driver.ok_or_else(|| de::Error::missing_field("driver"))?;
+        Ok(DriverSlot {
+            name,
+            enabled,
+            weight,
+            driver,
+        })
+    }
+}
+
+struct ErasedDriverSeed<H: Handedness>(DeserializeFn<H>);
+
+impl<'de, H: Handedness> DeserializeSeed<'de> for ErasedDriverSeed<H> {
+    type Value = Box<dyn RigDriverTraits<H>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0)(&mut deserializer).map_err(de::Error::custom)
+    }
+}
+
+#[test]
+fn rig_serialization_roundtrip() {
+    use crate::{
+        driver::RigDriver,
+        drivers::{Keyframe, Keyframes, Playback},
+        handedness::RightHanded,
+        rig::RigUpdateParams,
+        track::CameraTrack,
+        transform::Transform,
+    };
+    use glam::{Quat, Vec3};
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct Lift(f32);
+
+    impl<H: Handedness> RigDriver<H> for Lift {
+        fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+            let position = Vec3::from(params.parent.position) + Vec3::Y * self.0;
+            Transform::from_position_rotation(position, params.parent.rotation)
+        }
+    }
+
+    let mut track = CameraTrack::new();
+    track.record(
+        0.0,
+        Transform::from_position_rotation(Vec3::X, Quat::IDENTITY),
+    );
+    track.record(
+        1.0,
+        Transform::from_position_rotation(Vec3::Y, Quat::IDENTITY),
+    );
+
+    let origin = glam::DVec3::new(1e6, 0.0, -2e6);
+    let rig: CameraRig = CameraRig::builder()
+        .origin(origin)
+        .fixed_timestep(1.0 / 120.0)
+        .max_substeps(8)
+        .with(Keyframes::new([Keyframe::new(1.0, Vec3::Z, Quat::IDENTITY)]).looping(true))
+        .with(Playback::new(track).speed(0.5))
+        .with(Position::new(Vec3::new(1.0, 2.0, 3.0)))
+        .with(YawPitch::new().yaw_degrees(45.0).pitch_degrees(-30.0))
+        .with_named("smooth", Smooth::new_position_rotation(1.0, 1.0))
+        .with(Arm::new(Vec3::Z * 4.0))
+        .with(Lift(0.5))
+        .build();
+
+    assert!(DriverRegistry::new()
+        .serialize_rig(&rig, &mut serde_json::Serializer::new(Vec::new()))
+        .is_err());
+
+    let registry = DriverRegistry::new().with::<Lift>("Lift");
+
+    let mut json = Vec::new();
+    registry
+        .serialize_rig(&rig, &mut serde_json::Serializer::new(&mut json))
+        .unwrap();
+
+    let restored = registry
+        .deserialize_rig(&mut serde_json::Deserializer::from_slice(&json))
+        .unwrap();
+
+    assert_eq!(restored.drivers.len(), rig.drivers.len());
+    assert_eq!(restored.driver::<Lift>().0, 0.5);
+    assert_eq!(restored.driver_index("smooth"), Some(4));
+    assert!(restored.driver::<Keyframes>().clock.looping);
+    assert_eq!(restored.driver::<Keyframes>().keys().len(), 1);
+
+    let playback = restored.driver::<Playback<RightHanded>>();
+    assert_eq!(playback.clock.speed, 0.5);
+    assert_eq!(playback.track.len(), 2);
+
+    // The floating origin and fixed-timestep settings are kept.
+    assert_eq!(restored.origin(), origin.into());
+    assert_eq!(restored.fixed_timestep(), Some(1.0 / 120.0));
+    assert_eq!(restored.max_substeps(), 8);
+
+    let expected: Vec3 = rig.final_transform.position.into();
+    let actual: Vec3 = restored.final_transform.position.into();
+    assert!(expected.distance(actual) < 1e-5);
+    assert_eq!(
+        restored.world_transform().position,
+        rig.world_transform().position
+    );
+}
@@ -0,0 +1,465 @@
+//! Recording of camera paths, for replaying them with the [`Playback`] driver.
+//!
+//! Tracks can be stored in a compact binary format, or as CSV for editing by hand.
+//! Playback time is kept by a [`PlaybackClock`], which is shared with the [`Keyframes`] driver.
+//!
+//! [`Playback`]: ../drivers/struct.Playback.html
+//! [`PlaybackClock`]: struct.PlaybackClock.html
+//! [`Keyframes`]: ../drivers/struct.Keyframes.html
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, fmt::Write, marker::PhantomData};
+
+use glam::{Quat, Vec3};
+
+use crate::{handedness::Handedness, math, transform::Transform};
+
+// Identifies the binary format, followed by a version byte.
+const MAGIC: &[u8; 4] = b"DTRK";
+const VERSION: u8 = 1;
+
+// Time, position, and rotation.
+const FLOATS_PER_SAMPLE: usize = 8;
+
+const CSV_HEADER: &str = "time,x,y,z,qx,qy,qz,qw";
+
+/// A single transform in a [`CameraTrack`].
+///
+/// [`CameraTrack`]: struct.CameraTrack.html
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct TrackSample<H: Handedness> {
+    /// Seconds since the start of the track
+    pub time_seconds: f32,
+
+    pub transform: Transform<H>,
+}
+
+/// A recorded sequence of camera transforms, e.g. the output of [`CameraRig::update`].
+///
+/// ```
+/// use dolly::{prelude::*, track::CameraTrack};
+///
+/// let mut camera: CameraRig = CameraRig::builder()
+///     .with(Position::new(glam::Vec3::ZERO))
+///     .build();
+///
+/// let mut track = CameraTrack::new();
+/// for _ in 0..60 {
+///     let delta_time_seconds = 1.0 / 60.0;
+///     camera.driver_mut::<Position>().translate(glam::Vec3::X * delta_time_seconds);
+///     track.record(delta_time_seconds, camera.update(delta_time_seconds));
+/// }
+///
+/// let bytes = track.to_bytes();
+/// let track = CameraTrack::<RightHanded>::from_bytes(&bytes).unwrap();
+/// let camera: CameraRig = CameraRig::builder().with(Playback::new(track)).build();
+/// ```
+///
+/// [`CameraRig::update`]: ../rig/struct.CameraRig.html#method.update
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct CameraTrack<H: Handedness> {
+    samples: Vec<TrackSample<H>>,
+}
+
+impl<H: Handedness> Default for CameraTrack<H> {
+    fn default() -> Self {
+        Self {
+            samples: Vec::new(),
+        }
+    }
+}
+
+/// The reason a [`CameraTrack`] could not be read.
+///
+/// [`CameraTrack`]: struct.CameraTrack.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackError {
+    /// The data doesn't start with the header of the format.
+    InvalidHeader,
+
+    /// The binary data was written by a newer version of the format.
+    UnsupportedVersion(u8),
+
+    /// The binary data ends in the middle of a sample.
+    UnexpectedEnd,
+
+    /// A CSV line doesn't contain eight numbers. Lines are numbered from one.
+    InvalidLine(usize),
+
+    /// The time of the sample at `index` is lower than that of the previous one.
+    TimeNotIncreasing { index: usize },
+}
+
+impl fmt::Display for TrackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "not a camera track"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported camera track version {}", version)
+            }
+            Self::UnexpectedEnd => write!(f, "unexpected end of camera track"),
+            Self::InvalidLine(line) => write!(f, "invalid camera track sample on line {}", line),
+            Self::TimeNotIncreasing { index } => {
+                write!(
+                    f,
+                    "the time of sample #{} is lower than the previous",
+                    index
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TrackError {}
+
+impl<H: Handedness> CameraTrack<H> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a track from samples in chronological order.
+    pub fn from_samples(
+        samples: impl IntoIterator<Item = TrackSample<H>>,
+    ) -> Result<Self, TrackError> {
+        let samples: Vec<_> = samples.into_iter().collect();
+
+        for (index, pair) in samples.windows(2).enumerate() {
+            if pair[1].time_seconds < pair[0].time_seconds {
+                return Err(TrackError::TimeNotIncreasing { index: index + 1 });
+            }
+        }
+
+        Ok(Self { samples })
+    }
+
+    /// Append `transform`, `delta_time_seconds` after the previous sample.
+    /// The first sample is placed at `delta_time_seconds` after the start of the track.
+    pub fn record(&mut self, delta_time_seconds: f32, transform: Transform<H>) {
+        let time_seconds = self.duration_seconds() + delta_time_seconds.max(0.0);
+        self.samples.push(TrackSample {
+            time_seconds,
+            transform,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn samples(&self) -> &[TrackSample<H>] {
+        &self.samples
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// The time of the last sample.
+    pub fn duration_seconds(&self) -> f32 {
+        self.samples
+            .last()
+            .map_or(0.0, |sample| sample.time_seconds)
+    }
+
+    /// The transform at `time_seconds`, interpolated between the nearest samples,
+    /// or `None` if the track is empty. Times outside of the track are clamped.
+    pub fn sample(&self, time_seconds: f32) -> Option<Transform<H>> {
+        let next = self
+            .samples
+            .partition_point(|sample| sample.time_seconds <= time_seconds);
+
+        if next == 0 {
+            return self.samples.first().map(|sample| sample.transform);
+        }
+        if next == self.samples.len() {
+            return self.samples.last().map(|sample| sample.transform);
+        }
+
+        let a = &self.samples[next - 1];
+        let b = &self.samples[next];
+        let t = (time_seconds - a.time_seconds) / (b.time_seconds - a.time_seconds);
+
+        Some(a.transform.lerp(&b.transform, t))
+    }
+
+    /// Moves every sample by `offset`.
+    pub fn translate<V>(&mut self, offset: V)
+    where
+        V: Into<mint::Vector3<f32>>,
+    {
+        let offset = Vec3::from(offset.into());
+        for sample in self.samples.iter_mut() {
+            sample.transform.position = (Vec3::from(sample.transform.position) + offset).into();
+        }
+    }
+
+    /// Write the track in the binary format: the `DTRK` magic, a version byte,
+    /// the number of samples as a `u32`, and the time, position, and rotation
+    /// of every sample, as eight `f32`s. Numbers are little-endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(9 + self.samples.len() * FLOATS_PER_SAMPLE * 4);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(self.samples.len() as u32).to_le_bytes());
+
+        for sample in &self.samples {
+            for value in sample_to_floats(sample) {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        bytes
+    }
+
+    /// Read a track written by [`to_bytes`](#method.to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TrackError> {
+        let rest = bytes
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or(TrackError::InvalidHeader)?;
+
+        let (&version, rest) = rest.split_first().ok_or(TrackError::InvalidHeader)?;
+        if version != VERSION {
+            return Err(TrackError::UnsupportedVersion(version));
+        }
+
+        if rest.len() < 4 {
+            return Err(TrackError::InvalidHeader);
+        }
+        let (count, rest) = rest.split_at(4);
+        let count = u32::from_le_bytes(count.try_into().unwrap()) as usize;
+
+        let sample_size = FLOATS_PER_SAMPLE * 4;
+        if rest.len() < count.saturating_mul(sample_size) {
+            return Err(TrackError::UnexpectedEnd);
+        }
+
+        Self::from_samples(rest.chunks_exact(sample_size).take(count).map(|chunk| {
+            let mut floats = [0.0; FLOATS_PER_SAMPLE];
+            for (value, bytes) in floats.iter_mut().zip(chunk.chunks_exact(4)) {
+                *value = f32::from_le_bytes(bytes.try_into().unwrap());
+            }
+            sample_from_floats(floats)
+        }))
+    }
+
+    /// Write the track as CSV, with a header line, and one sample per line.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        csv.push_str(CSV_HEADER);
+        csv.push('\n');
+
+        for sample in &self.samples {
+            let [time, x, y, z, qx, qy, qz, qw] = sample_to_floats(sample);
+            // Writing to a `String` can't fail.
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{}",
+                time, x, y, z, qx, qy, qz, qw
+            );
+        }
+
+        csv
+    }
+
+    /// Read a track written by [`to_csv`](#method.to_csv). Empty lines are skipped.
+    pub fn from_csv(csv: &str) -> Result<Self, TrackError> {
+        let mut lines = csv.lines().enumerate();
+
+        match lines.next() {
+            Some((_, header)) if header.trim() == CSV_HEADER => {}
+            _ => return Err(TrackError::InvalidHeader),
+        }
+
+        let mut samples = Vec::new();
+        for (index, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut floats = [0.0; FLOATS_PER_SAMPLE];
+            let mut values = line.split(',');
+            for value in floats.iter_mut() {
+                *value = values
+                    .next()
+                    .and_then(|value| value.trim().parse().ok())
+                    .ok_or(TrackError::InvalidLine(index + 1))?;
+            }
+            if values.next().is_some() {
+                return Err(TrackError::InvalidLine(index + 1));
+            }
+
+            samples.push(sample_from_floats(floats));
+        }
+
+        Self::from_samples(samples)
+    }
+}
+
+/// Time within an animation, with play, pause, seek, and looping controls.
+///
+/// The clock doesn't know the duration of the animation, so it's passed to the methods
+/// which need it. Times are kept within `[0, duration]`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlaybackClock {
+    /// Playback rate; 1.0 plays at the recorded or authored speed, and negative values play in reverse
+    pub speed: f32,
+
+    /// Whether time advances on `update`
+    pub playing: bool,
+
+    /// Whether to wrap around when reaching either end of the animation
+    pub looping: bool,
+
+    time_seconds: f32,
+}
+
+impl Default for PlaybackClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlaybackClock {
+    /// A playing, non-looping clock at the start of the animation.
+    pub fn new() -> Self {
+        Self {
+            speed: 1.0,
+            playing: true,
+            looping: false,
+            time_seconds: 0.0,
+        }
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// The current time within the animation.
+    pub fn time_seconds(&self) -> f32 {
+        self.time_seconds
+    }
+
+    /// Jump to `time_seconds`, e.g. for scrubbing. Wraps around when looping, and clamps otherwise.
+    pub fn seek(&mut self, time_seconds: f32, duration_seconds: f32) {
+        self.time_seconds = if self.looping && duration_seconds > 0.0 {
+            math::rem_euclid(time_seconds, duration_seconds)
+        } else {
+            time_seconds.clamp(0.0, duration_seconds)
+        };
+    }
+
+    /// Whether a non-looping animation has been played to the end, in the direction of `speed`.
+    pub fn is_finished(&self, duration_seconds: f32) -> bool {
+        !self.looping
+            && if self.speed < 0.0 {
+                self.time_seconds <= 0.0
+            } else {
+                self.time_seconds >= duration_seconds
+            }
+    }
+
+    /// Advances time by `delta_time_seconds` scaled by `speed`, if playing.
+    ///
+    /// Calls `passed` with the start and end of every span of time covered along the way,
+    /// in playback order: once, or twice when wrapping around. Does nothing when paused.
+    pub(crate) fn advance(
+        &mut self,
+        delta_time_seconds: f32,
+        duration_seconds: f32,
+        mut passed: impl FnMut(f32, f32),
+    ) {
+        if !self.playing {
+            return;
+        }
+
+        let from = self.time_seconds;
+        let to = from + delta_time_seconds * self.speed;
+
+        if self.looping && duration_seconds > 0.0 {
+            if to > duration_seconds {
+                passed(from, duration_seconds);
+                passed(0.0, math::rem_euclid(to, duration_seconds));
+            } else if to < 0.0 {
+                passed(from, 0.0);
+                passed(duration_seconds, math::rem_euclid(to, duration_seconds));
+            } else {
+                passed(from, to);
+            }
+        } else {
+            passed(from, to.clamp(0.0, duration_seconds));
+        }
+
+        self.seek(to, duration_seconds);
+    }
+}
+
+fn sample_to_floats<H: Handedness>(sample: &TrackSample<H>) -> [f32; FLOATS_PER_SAMPLE] {
+    let mint::Point3 { x, y, z } = sample.transform.position;
+    let [qx, qy, qz, qw] = Quat::from(sample.transform.rotation).to_array();
+    [sample.time_seconds, x, y, z, qx, qy, qz, qw]
+}
+
+fn sample_from_floats<H: Handedness>(floats: [f32; FLOATS_PER_SAMPLE]) -> TrackSample<H> {
+    let [time_seconds, x, y, z, qx, qy, qz, qw] = floats;
+    TrackSample {
+        time_seconds,
+        transform: Transform {
+            position: mint::Point3 { x, y, z },
+            rotation: Quat::from_xyzw(qx, qy, qz, qw).into(),
+            phantom: PhantomData,
+        },
+    }
+}
+
+#[test]
+fn track_roundtrips_through_bytes_and_csv() {
+    use crate::handedness::RightHanded;
+
+    let mut track = CameraTrack::<RightHanded>::new();
+    for i in 0..10 {
+        let rotation = Quat::from_rotation_y(i as f32 * 0.1);
+        track.record(
+            0.1,
+            Transform::from_position_rotation(Vec3::new(i as f32, 0.5, -1.0), rotation),
+        );
+    }
+
+    let from_bytes = CameraTrack::<RightHanded>::from_bytes(&track.to_bytes()).unwrap();
+    let from_csv = CameraTrack::<RightHanded>::from_csv(&track.to_csv()).unwrap();
+
+    for other in [&from_bytes, &from_csv] {
+        assert_eq!(other.len(), track.len());
+        for (a, b) in track.samples().iter().zip(other.samples()) {
+            assert_eq!(a.time_seconds, b.time_seconds);
+            assert_eq!(a.transform.position, b.transform.position);
+            assert_eq!(a.transform.rotation, b.transform.rotation);
+        }
+    }
+
+    let halfway: Vec3 = track.sample(0.45).unwrap().position.into();
+    assert!(halfway.distance(Vec3::new(3.5, 0.5, -1.0)) < 1e-5);
+
+    let bytes = track.to_bytes();
+    assert_eq!(
+        CameraTrack::<RightHanded>::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+        TrackError::UnexpectedEnd
+    );
+    assert_eq!(
+        CameraTrack::<RightHanded>::from_csv("time,x,y,z,qx,qy,qz,qw\n0,1,2\n").unwrap_err(),
+        TrackError::InvalidLine(2)
+    );
+}
@@ -1,14 +1,17 @@
 use core::fmt::Debug;
-use glam::{Quat, Vec3};
-use std::marker::PhantomData;
+use core::marker::PhantomData;
+use glam::{DQuat, DVec3, Mat4, Quat, Vec3};
 
-use crate::handedness::Handedness;
+use crate::{handedness::Handedness, util::slerp_shortest};
 
 /// A thin wrapper over a `Point3<f32>` and a `Quaternion<f32>`
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct Transform<H: Handedness> {
     pub position: mint::Point3<f32>,
     pub rotation: mint::Quaternion<f32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub phantom: PhantomData<H>,
 }
 
@@ -63,6 +66,44 @@ impl<H: Handedness> Transform<H> {
         From::from((rotation * H::FORWARD).into())
     }
 
+    /// Returns the camera-to-world matrix.
+    ///
+    /// The camera space follows the convention of `H`: the camera looks along -Z
+    /// in right-handed systems, and along +Z in left-handed ones.
+    pub fn camera_to_world_matrix<M>(&self) -> M
+    where
+        M: From<mint::ColumnMatrix4<f32>>,
+    {
+        let matrix = Mat4::from_rotation_translation(self.rotation.into(), self.position.into());
+        From::from(matrix.into())
+    }
+
+    /// Returns the world-to-camera (view) matrix; the inverse of [`camera_to_world_matrix`].
+    ///
+    /// [`camera_to_world_matrix`]: struct.Transform.html#method.camera_to_world_matrix
+    pub fn view_matrix<M>(&self) -> M
+    where
+        M: From<mint::ColumnMatrix4<f32>>,
+    {
+        let rotation = Quat::from(self.rotation).inverse();
+        let translation = rotation * -Vec3::from(self.position);
+        let matrix = Mat4::from_rotation_translation(rotation, translation);
+        From::from(matrix.into())
+    }
+
+    /// Interpolates between `self` and `other`, linearly for the position,
+    /// and along the shortest arc for the rotation.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let position = Vec3::from(self.position).lerp(other.position.into(), t);
+        let rotation = slerp_shortest(self.rotation.into(), other.rotation.into(), t);
+
+        Self {
+            position: position.into(),
+            rotation: rotation.into(),
+            phantom: PhantomData,
+        }
+    }
+
     pub const IDENTITY: Transform<H> = Transform {
         position: mint::Point3 {
             x: 0.0,
@@ -80,3 +121,89 @@ impl<H: Handedness> Transform<H> {
         phantom: PhantomData,
     };
 }
+
+/// A double-precision counterpart of [`Transform`], for positions in large worlds.
+///
+/// [`CameraRig`]s run in single precision relative to a floating origin;
+/// see [`CameraRig::world_transform`].
+///
+/// [`Transform`]: struct.Transform.html
+/// [`CameraRig`]: ../rig/struct.CameraRig.html
+/// [`CameraRig::world_transform`]: ../rig/struct.CameraRig.html#method.world_transform
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct DTransform<H: Handedness> {
+    pub position: mint::Point3<f64>,
+    pub rotation: mint::Quaternion<f64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub phantom: PhantomData<H>,
+}
+
+impl<H: Handedness> DTransform<H> {
+    pub fn from_position_rotation<P, Q>(position: P, rotation: Q) -> Self
+    where
+        P: Into<mint::Point3<f64>>,
+        Q: Into<mint::Quaternion<f64>>,
+    {
+        Self {
+            position: position.into(),
+            rotation: rotation.into(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Places a single-precision `transform`, relative to `origin`, in the world.
+    pub fn from_relative<P>(transform: &Transform<H>, origin: P) -> Self
+    where
+        P: Into<mint::Point3<f64>>,
+    {
+        let position = DVec3::from(origin.into()) + Vec3::from(transform.position).as_dvec3();
+        let rotation = Quat::from(transform.rotation);
+
+        Self {
+            position: position.into(),
+            rotation: DQuat::from_xyzw(
+                rotation.x as f64,
+                rotation.y as f64,
+                rotation.z as f64,
+                rotation.w as f64,
+            )
+            .into(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a single-precision transform relative to `origin`,
+    /// e.g. the origin used for rendering.
+    ///
+    /// Precision is retained as long as `origin` is close to the transform.
+    pub fn relative_to<P>(&self, origin: P) -> Transform<H>
+    where
+        P: Into<mint::Point3<f64>>,
+    {
+        let position = (DVec3::from(self.position) - DVec3::from(origin.into())).as_vec3();
+        let rotation = DQuat::from(self.rotation);
+
+        Transform {
+            position: position.into(),
+            rotation: Quat::from_xyzw(
+                rotation.x as f32,
+                rotation.y as f32,
+                rotation.z as f32,
+                rotation.w as f32,
+            )
+            .into(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// +/-Z
+    pub fn forward<V>(&self) -> V
+    where
+        V: From<mint::Vector3<f64>>,
+    {
+        let rotation: DQuat = self.rotation.into();
+        From::from((rotation * H::FORWARD.as_dvec3()).into())
+    }
+}
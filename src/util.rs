@@ -1,49 +1,211 @@
 use glam::{Mat3, Quat, Vec3};
 
-use crate::prelude::Handedness;
+use crate::{math, prelude::Handedness};
 
 pub(crate) trait Interpolate {
+    /// Rate of change used by spring smoothing
+    type Velocity: Copy + Default + core::fmt::Debug;
+
     fn interpolate(self, other: Self, t: f32) -> Self;
+
+    /// Advances a critically damped spring pulling `self` towards `target`.
+    fn spring_towards(
+        self,
+        target: Self,
+        velocity: &mut Self::Velocity,
+        omega: f32,
+        delta_time_seconds: f32,
+    ) -> Self;
+}
+
+impl Interpolate for f32 {
+    type Velocity = f32;
+
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    fn spring_towards(
+        self,
+        target: Self,
+        velocity: &mut Self::Velocity,
+        omega: f32,
+        delta_time_seconds: f32,
+    ) -> Self {
+        // Closed-form solution, exact for any time step while the target is stationary.
+        let offset = self - target;
+        let j = *velocity + offset * omega;
+        let decay = math::exp(-omega * delta_time_seconds);
+
+        *velocity = (*velocity - j * (omega * delta_time_seconds)) * decay;
+        target + (offset + j * delta_time_seconds) * decay
+    }
 }
 
 impl Interpolate for Vec3 {
+    type Velocity = Vec3;
+
     fn interpolate(self, other: Self, t: f32) -> Self {
         Vec3::lerp(self, other, t)
     }
+
+    fn spring_towards(
+        self,
+        target: Self,
+        velocity: &mut Self::Velocity,
+        omega: f32,
+        delta_time_seconds: f32,
+    ) -> Self {
+        // See the `f32` implementation.
+        let offset = self - target;
+        let j = *velocity + offset * omega;
+        let decay = math::exp(-omega * delta_time_seconds);
+
+        *velocity = (*velocity - j * (omega * delta_time_seconds)) * decay;
+        target + (offset + j * delta_time_seconds) * decay
+    }
 }
 
 impl Interpolate for Quat {
+    /// Angular velocity, as a scaled axis
+    type Velocity = Vec3;
+
     fn interpolate(self, other: Self, t: f32) -> Self {
-        // Technically should be a `slerp` for framerate independence, but the latter
-        // will rotate in the negative direction when interpolating a 180..360 degree rotation
-        // to the 0..180 range. See the comment about `yaw_degrees` in `YawPitch` for more details.
-        Quat::lerp(self.normalize(), other.normalize(), t).normalize()
+        slerp_shortest(self, other, t)
+    }
+
+    fn spring_towards(
+        self,
+        target: Self,
+        velocity: &mut Self::Velocity,
+        omega: f32,
+        delta_time_seconds: f32,
+    ) -> Self {
+        // Run the spring on the rotation from the target, expressed as a scaled axis.
+        let mut offset = self.normalize() * target.normalize().inverse();
+        if offset.w < 0.0 {
+            offset = -offset;
+        }
+
+        let offset =
+            offset
+                .to_scaled_axis()
+                .spring_towards(Vec3::ZERO, velocity, omega, delta_time_seconds);
+
+        (Quat::from_scaled_axis(offset) * target).normalize()
     }
 }
 
-pub(crate) struct ExpSmoothingParams {
+/// Spherical interpolation along the shortest arc.
+///
+/// `q` and `-q` encode the same rotation, so the target is flipped into the hemisphere
+/// of `from` first; otherwise the interpolation could take the long way around.
+pub(crate) fn slerp_shortest(from: Quat, to: Quat, t: f32) -> Quat {
+    let from = from.normalize();
+    let to = to.normalize();
+    let to = if from.dot(to) < 0.0 { -to } else { to };
+
+    from.slerp(to, t).normalize()
+}
+
+// An ad-hoc multiplier to make default smoothness parameters
+// produce good-looking results.
+const SMOOTHNESS_MULT: f32 = 8.0;
+
+// A critically damped spring released at rest covers half the distance to its target
+// after `omega * t` reaches this value, i.e. the root of `(1 + x) * exp(-x) = 0.5`.
+const SPRING_HALF_LIFE_OMEGA: f32 = 1.678_347;
+
+// Time in which exponential smoothing covers half the distance to a stationary target.
+fn smoothing_half_life(smoothness: f32) -> f32 {
+    smoothness * core::f32::consts::LN_2 / SMOOTHNESS_MULT
+}
+
+/// Calculates the exponential blending factor towards a target, based on frame time.
+pub(crate) fn exp_smoothing_factor(smoothness: f32, delta_time_seconds: f32) -> f32 {
+    1.0 - math::exp(-SMOOTHNESS_MULT * delta_time_seconds / smoothness.max(1e-5))
+}
+
+/// How smoothing drivers approach their targets.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SmoothingModel {
+    /// First-order exponential decay.
+    ///
+    /// Responds immediately to changes in the target, but the output velocity jumps
+    /// whenever the target starts or stops moving.
+    #[default]
+    Exponential,
+
+    /// Critically damped spring.
+    ///
+    /// Keeps track of velocity, so that the output starts and stops moving smoothly,
+    /// at the cost of a slower initial response. Does not overshoot a stationary target.
+    ///
+    /// Every smoothness factor of the driver sets the half-life of its own channel:
+    /// starting at rest, the spring covers half the distance to a stationary target
+    /// in the same time as exponential smoothing with that factor, and settles within 1%
+    /// of it in about four half-lives.
+    Spring,
+}
+
+pub(crate) struct SmoothingParams {
     pub smoothness: f32,
     pub output_offset_scale: f32,
     pub delta_time_seconds: f32,
+    pub model: SmoothingModel,
 }
 
-#[derive(Default, Debug)]
-pub(crate) struct ExpSmoothed<T: Interpolate + Copy + std::fmt::Debug>(Option<T>);
+#[derive(Clone, Default, Debug)]
+pub(crate) struct Smoothed<T: Interpolate + Copy + core::fmt::Debug> {
+    value: Option<T>,
+    velocity: T::Velocity,
+}
+
+impl<T: Interpolate + Copy + core::fmt::Debug> Smoothed<T> {
+    /// The most recent smoothed value, if any.
+    pub(crate) fn get(&self) -> Option<T> {
+        self.value
+    }
 
-impl<T: Interpolate + Copy + std::fmt::Debug> ExpSmoothed<T> {
-    pub(crate) fn exp_smooth_towards(&mut self, other: &T, params: ExpSmoothingParams) -> T {
-        // An ad-hoc multiplier to make default smoothness parameters
-        // produce good-looking results.
-        const SMOOTHNESS_MULT: f32 = 8.0;
+    /// Forgets the smoothed value and velocity, so that the next update snaps to the target.
+    pub(crate) fn reset(&mut self) {
+        self.value = None;
+        self.velocity = Default::default();
+    }
+
+    /// Applies `f` to the smoothed value, keeping the velocity.
+    pub(crate) fn map(&mut self, f: impl FnOnce(T) -> T) {
+        self.value = self.value.map(f);
+    }
 
-        // Calculate the exponential blending based on frame time
-        let interp_t = 1.0
-            - (-SMOOTHNESS_MULT * params.delta_time_seconds / params.smoothness.max(1e-5)).exp();
+    pub(crate) fn smooth_towards(&mut self, other: &T, params: SmoothingParams) -> T {
+        let prev = self.value.unwrap_or(*other);
 
-        let prev = self.0.unwrap_or(*other);
-        let smooth = prev.interpolate(*other, interp_t);
+        let smooth = match params.model {
+            SmoothingModel::Exponential => {
+                self.velocity = Default::default();
 
-        self.0 = Some(smooth);
+                let interp_t = exp_smoothing_factor(params.smoothness, params.delta_time_seconds);
+                prev.interpolate(*other, interp_t)
+            }
+            SmoothingModel::Spring => {
+                if params.smoothness > 0.0 {
+                    let omega = SPRING_HALF_LIFE_OMEGA / smoothing_half_life(params.smoothness);
+                    prev.spring_towards(
+                        *other,
+                        &mut self.velocity,
+                        omega,
+                        params.delta_time_seconds,
+                    )
+                } else {
+                    self.velocity = Default::default();
+                    *other
+                }
+            }
+        };
+
+        self.value = Some(smooth);
 
         #[allow(clippy::float_cmp)]
         if params.output_offset_scale != 1.0 {
@@ -60,20 +222,48 @@ where
     Q: From<mint::Quaternion<f32>>,
 {
     let forward: Vec3 = forward.into().into();
-
-    let result = forward
-        .try_normalize()
-        .and_then(|forward| {
-            let right =
-                H::right_from_up_and_forward::<Vec3, Vec3>(Vec3::Y, forward).try_normalize()?;
-            let up = H::up_from_right_and_forward(right, forward);
-            Some(Quat::from_mat3(&Mat3::from_cols(
-                right,
-                up,
-                forward * H::FORWARD_Z_SIGN,
-            )))
-        })
-        .unwrap_or_default();
+    let result = look_at_with_up::<H>(forward, Vec3::Y).unwrap_or_default();
 
     From::from(result.into())
 }
+
+/// Rotation facing `forward`, with its up vector as close to `up` as possible.
+/// Returns `None` if the vectors are degenerate or parallel.
+pub(crate) fn look_at_with_up<H: Handedness>(forward: Vec3, up: Vec3) -> Option<Quat> {
+    let forward = forward.try_normalize()?;
+    let right = H::right_from_up_and_forward::<Vec3, Vec3>(up, forward).try_normalize()?;
+    let up = H::up_from_right_and_forward(right, forward);
+
+    Some(Quat::from_mat3(&Mat3::from_cols(
+        right,
+        up,
+        forward * H::FORWARD_Z_SIGN,
+    )))
+}
+
+fn hash(seed: u32, i: i32) -> u32 {
+    let mut h = seed ^ (i as u32).wrapping_mul(0x9e37_79b1);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb_352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846c_a68b);
+    h ^= h >> 16;
+    h
+}
+
+/// One-dimensional gradient noise, roughly in the `[-1, 1]` range.
+/// Deterministic for a given `seed`.
+pub(crate) fn gradient_noise(seed: u32, x: f32) -> f32 {
+    let gradient = |i: i32| (hash(seed, i) as f32 / u32::MAX as f32) * 2.0 - 1.0;
+
+    let i0 = math::floor(x);
+    let f = x - i0;
+    let i0 = i0 as i32;
+
+    let g0 = gradient(i0) * f;
+    let g1 = gradient(i0.wrapping_add(1)) * (f - 1.0);
+    let fade = f * f * f * (f * (f * 6.0 - 15.0) + 10.0);
+
+    // The 1D gradient noise peaks at 0.5; rescale it to roughly unit range.
+    (g0 + (g1 - g0) * fade) * 2.0
+}